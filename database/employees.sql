@@ -39,7 +39,7 @@ DROP TABLE IF EXISTS dept_emp,
 /*!50503 select CONCAT('storage engine: ', @@default_storage_engine) as INFO */;
 
 CREATE TABLE employees (
-    emp_no      INT             NOT NULL,
+    emp_no      INT             NOT NULL AUTO_INCREMENT,
     birth_date  DATE            NOT NULL,
     first_name  VARCHAR(14)     NOT NULL,
     last_name   VARCHAR(16)     NOT NULL,
@@ -72,7 +72,7 @@ CREATE TABLE dept_emp (
     to_date     DATE            NOT NULL,
     FOREIGN KEY (emp_no)  REFERENCES employees   (emp_no)  ON DELETE CASCADE,
     FOREIGN KEY (dept_no) REFERENCES departments (dept_no) ON DELETE CASCADE,
-    PRIMARY KEY (emp_no,dept_no)
+    PRIMARY KEY (emp_no,dept_no,from_date)
 );
 
 CREATE TABLE titles (
@@ -99,6 +99,45 @@ CREATE TABLE rate_limits (
     PRIMARY KEY (username)
 ); 
 
+CREATE TABLE terminations (
+    emp_no              INT             NOT NULL,
+    termination_date    DATE            NOT NULL,
+    reason              VARCHAR(255)    NOT NULL,
+    rehire_date         DATE,
+    FOREIGN KEY (emp_no) REFERENCES employees (emp_no) ON DELETE CASCADE,
+    PRIMARY KEY (emp_no, termination_date)
+);
+
+# emp_no has no foreign key on purpose: the history must survive a purge
+CREATE TABLE audit_log (
+    id              BIGINT          NOT NULL AUTO_INCREMENT,
+    actor           VARCHAR(256)    NOT NULL,
+    action          VARCHAR(32)     NOT NULL,
+    resource        VARCHAR(32)     NOT NULL,
+    resource_key    JSON            NOT NULL,
+    emp_no          INT,
+    before_data     JSON,
+    after_data      JSON,
+    request_id      VARCHAR(64)     NOT NULL,
+    created_at      DATETIME        NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    KEY (emp_no, id)
+);
+
+# stored responses for retried writes; status_code is NULL while the first request runs
+CREATE TABLE idempotency_keys (
+    actor           VARCHAR(256)    NOT NULL,
+    idempotency_key VARCHAR(255)    NOT NULL,
+    request_hash    CHAR(64)        NOT NULL,
+    status_code     SMALLINT,
+    content_type    VARCHAR(255),
+    etag            VARCHAR(64),
+    response_body   LONGBLOB,
+    created_at      DATETIME        NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (actor, idempotency_key),
+    KEY (created_at)
+);
+
 CREATE OR REPLACE VIEW dept_emp_latest_date AS
     SELECT emp_no, MAX(from_date) AS from_date, MAX(to_date) AS to_date
     FROM dept_emp
@@ -128,4 +167,9 @@ source load_salaries1.dump ;
 source load_salaries2.dump ;
 source load_salaries3.dump ;
 
+SELECT 'APPLYING soft delete columns' as 'INFO';
+source migrations/002_soft_delete.sql ;
+SELECT 'APPLYING row version columns' as 'INFO';
+source migrations/004_row_version.sql ;
+
 source show_elapsed.sql ;
\ No newline at end of file
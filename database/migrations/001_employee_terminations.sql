@@ -0,0 +1,21 @@
+--
+-- Termination and rehire support for an existing employees database.
+-- Fresh installs get the same structure from employees.sql.
+--
+
+USE employees;
+
+-- A rehired employee can return to a department they worked in before,
+-- so dept_emp needs from_date in its key to keep both intervals.
+ALTER TABLE dept_emp
+    DROP PRIMARY KEY,
+    ADD PRIMARY KEY (emp_no, dept_no, from_date);
+
+CREATE TABLE IF NOT EXISTS terminations (
+    emp_no              INT             NOT NULL,
+    termination_date    DATE            NOT NULL,
+    reason              VARCHAR(255)    NOT NULL,
+    rehire_date         DATE,
+    FOREIGN KEY (emp_no) REFERENCES employees (emp_no) ON DELETE CASCADE,
+    PRIMARY KEY (emp_no, termination_date)
+);
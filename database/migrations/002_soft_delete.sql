@@ -0,0 +1,13 @@
+--
+-- Soft delete support. Rows with a deleted_at timestamp are hidden from normal API reads.
+-- employees.sql applies this after loading the dumps, since their INSERTs don't name columns.
+--
+
+USE employees;
+
+ALTER TABLE employees    ADD COLUMN deleted_at DATETIME;
+ALTER TABLE departments  ADD COLUMN deleted_at DATETIME;
+ALTER TABLE dept_manager ADD COLUMN deleted_at DATETIME;
+ALTER TABLE dept_emp     ADD COLUMN deleted_at DATETIME;
+ALTER TABLE titles       ADD COLUMN deleted_at DATETIME;
+ALTER TABLE salaries     ADD COLUMN deleted_at DATETIME;
@@ -0,0 +1,21 @@
+--
+-- Audit trail of every write made through the API.
+-- emp_no has no foreign key on purpose: the history must survive a purge.
+--
+
+USE employees;
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id              BIGINT          NOT NULL AUTO_INCREMENT,
+    actor           VARCHAR(256)    NOT NULL,
+    action          VARCHAR(32)     NOT NULL,
+    resource        VARCHAR(32)     NOT NULL,
+    resource_key    JSON            NOT NULL,
+    emp_no          INT,
+    before_data     JSON,
+    after_data      JSON,
+    request_id      VARCHAR(64)     NOT NULL,
+    created_at      DATETIME        NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    KEY (emp_no, id)
+);
@@ -0,0 +1,14 @@
+--
+-- Optimistic concurrency. Every API write bumps row_version, and updates must name the
+-- version they were based on (If-Match or "version"), so stale edits are rejected.
+-- employees.sql applies this after loading the dumps, since their INSERTs don't name columns.
+--
+
+USE employees;
+
+ALTER TABLE employees    ADD COLUMN row_version INT NOT NULL DEFAULT 1;
+ALTER TABLE departments  ADD COLUMN row_version INT NOT NULL DEFAULT 1;
+ALTER TABLE dept_manager ADD COLUMN row_version INT NOT NULL DEFAULT 1;
+ALTER TABLE dept_emp     ADD COLUMN row_version INT NOT NULL DEFAULT 1;
+ALTER TABLE titles       ADD COLUMN row_version INT NOT NULL DEFAULT 1;
+ALTER TABLE salaries     ADD COLUMN row_version INT NOT NULL DEFAULT 1;
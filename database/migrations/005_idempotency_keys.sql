@@ -0,0 +1,21 @@
+--
+-- Responses to writes sent with an Idempotency-Key header, so a retried request gets the
+-- original result instead of running again. Keys belong to the JWT subject that sent them.
+-- status_code is NULL while the first request is still running. Rows older than the
+-- configured window (idempotency.window_secs) are deleted by the API.
+--
+
+USE employees;
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    actor           VARCHAR(256)    NOT NULL,
+    idempotency_key VARCHAR(255)    NOT NULL,
+    request_hash    CHAR(64)        NOT NULL,
+    status_code     SMALLINT,
+    content_type    VARCHAR(255),
+    etag            VARCHAR(64),
+    response_body   LONGBLOB,
+    created_at      DATETIME        NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (actor, idempotency_key),
+    KEY (created_at)
+);
@@ -0,0 +1,13 @@
+--
+-- Lets the API allocate employee numbers: a create without emp_no inserts NULL and
+-- AUTO_INCREMENT picks the next one, so concurrent creators can't collide. Explicit numbers
+-- (imports, old clients) still insert as before and move the counter past themselves.
+-- The column is referenced by foreign keys, so they are unchecked while it is modified;
+-- its type does not change.
+--
+
+USE employees;
+
+SET FOREIGN_KEY_CHECKS = 0;
+ALTER TABLE employees MODIFY emp_no INT NOT NULL AUTO_INCREMENT;
+SET FOREIGN_KEY_CHECKS = 1;
@@ -138,6 +138,8 @@ delimiter //
 --
 -- shows the departments with the number of employees
 -- per department
+-- (the API's GET /departments/summary, or POST /departments
+-- {"action": "summary"}, adds average salary, titles and as_of)
 --
 create procedure show_departments()
 modifies sql data
@@ -0,0 +1,91 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use serde_json::{to_value, Value};
+use sqlx::{query, FromRow, MySql, Transaction};
+use tracing::{error, instrument};
+
+use crate::auth::Claims;
+use crate::error::ApiError;
+use crate::request_id::RequestId;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AuditRecord {
+    pub id: i64,
+    pub actor: String,
+    pub action: String,
+    pub resource: String,
+    pub resource_key: Value,
+    pub emp_no: Option<i32>,
+    pub before_data: Option<Value>,
+    pub after_data: Option<Value>,
+    pub request_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+// Everything POST /audit accepts, selected by the "action" field.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditQuery {
+    EmployeeHistory {
+        emp_no: i32,
+        #[serde(default = "default_history_limit")]
+        limit: i64,
+    },
+}
+
+fn default_history_limit() -> i64 {
+    50
+}
+
+// Who is making the change and under which request, carried into every write.
+#[derive(Debug, Clone)]
+pub struct AuditContext {
+    pub actor: String,
+    pub request_id: String,
+}
+
+impl AuditContext {
+    pub fn new(claims: &Claims, request_id: &RequestId) -> Self {
+        AuditContext {
+            actor: claims.sub.clone(),
+            request_id: request_id.0.clone(),
+        }
+    }
+
+    // Writes one audit row inside the caller's transaction, so the entry only exists
+    // if the change it describes was committed.
+    #[instrument(name = "db.audit", skip_all, fields(action = action, resource = resource))]
+    pub async fn record<T: Serialize>(
+        &self,
+        tx: &mut Transaction<'_, MySql>,
+        action: &str,
+        resource: &str,
+        key: Value,
+        before: Option<T>,
+        after: Option<T>,
+    ) -> Result<(), ApiError> {
+        let emp_no = key.get("emp_no").and_then(Value::as_i64).map(|emp_no| emp_no as i32);
+        let before_data = before.map(to_value).transpose().map_err(ApiError::internal)?;
+        let after_data = after.map(to_value).transpose().map_err(ApiError::internal)?;
+
+        let query_str = "INSERT INTO audit_log (actor, action, resource, resource_key, emp_no, before_data, after_data, request_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?)";
+
+        query(query_str)
+            .bind(&self.actor)
+            .bind(action)
+            .bind(resource)
+            .bind(key)
+            .bind(emp_no)
+            .bind(before_data)
+            .bind(after_data)
+            .bind(&self.request_id)
+            .execute(&mut **tx)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                error!(error = ?e, "Error writing audit log");
+                ApiError::from(e)
+            })
+    }
+}
@@ -0,0 +1,82 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+use futures::future::ready;
+use serde::{Deserialize, Serialize};
+use jsonwebtoken::{decode, Validation, DecodingKey, errors::Error, errors::ErrorKind};
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::config::JwtConfig;
+use crate::db::AppState;
+use crate::error::ApiError;
+use crate::metrics;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Claims {
+    pub sub: String, // Username
+    pub exp: usize, // Expiration time (Unix timestamp)
+    #[serde(default)]
+    pub role: Option<String>, // "admin" unlocks deleted rows and hard purges
+}
+
+impl Claims {
+    pub fn is_admin(&self) -> bool {
+        self.role.as_deref() == Some("admin")
+    }
+
+    pub fn require_admin(&self) -> Result<(), ApiError> {
+        if self.is_admin() {
+            Ok(())
+        } else {
+            metrics::auth_rejected(403, "admin_required");
+            Err(ApiError::Forbidden("Admin role required".to_string()))
+        }
+    }
+}
+
+pub fn validate_jwt(token: &str, config: &JwtConfig) -> Result<Claims, Error> {
+    let mut validation = Validation::default();
+    validation.leeway = config.leeway_secs;
+    match decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(config.secret.as_bytes()),
+        &validation,
+    ) {
+        Ok(c) => Ok(c.claims),
+        Err(e) => {
+            match e.kind() {
+                ErrorKind::ExpiredSignature => Err(Error::from(ErrorKind::ExpiredSignature)),
+                _ => Err(e),
+            }
+        }
+    }
+}
+
+impl FromRequestParts<Arc<AppState>> for Claims {
+    type Rejection = ApiError;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
+        let result = parts.headers.get(axum::http::header::AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .filter(|header_str| header_str.starts_with("Bearer "))
+            .map(|header_str| header_str.trim_start_matches("Bearer "))
+            .map_or(Err(("missing_token", "Missing bearer token")), |token| {
+                match validate_jwt(token, &state.config.jwt) {
+                    Ok(claims) => Ok(claims),
+                    Err(e) => {
+                        match e.kind() {
+                            ErrorKind::ExpiredSignature => Err(("expired", "Token expired")),
+                            _ => Err(("invalid", "Invalid token")),
+                        }
+                    }
+                }
+            })
+            .map_err(|(reason, message)| {
+                metrics::auth_rejected(401, reason);
+                ApiError::Unauthorized(message.to_string())
+            });
+        ready(result)
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::departments::models::DepartmentRequest;
+use crate::dept_emp::models::DeptEmpRequest;
+use crate::dept_manager::models::DeptManagerRequest;
+use crate::employees::models::EmployeeRequest;
+use crate::error::ErrorBody;
+use crate::salaries::models::SalaryRequest;
+use crate::titles::models::TitleRequest;
+use crate::validation::{Validate, ValidationErrors};
+
+// Enough for a new hire and their first rows, without holding locks for long.
+pub const MAX_OPERATIONS: usize = 100;
+
+// POST /batch: actions run in order in one transaction; the first failure rolls back all of them.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchRequest {
+    pub operations: Vec<BatchOperation>,
+}
+
+impl Validate for BatchRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if self.operations.is_empty() || self.operations.len() > MAX_OPERATIONS {
+            errors.add("operations", format!("must hold between 1 and {} operations", MAX_OPERATIONS));
+        }
+        errors.into_result()
+    }
+}
+
+// One action request, as POST /<resource> takes it, plus the resource it is for, e.g.
+// {"resource": "titles", "action": "create", "title": {...}}.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "resource", rename_all = "snake_case")]
+pub enum BatchOperation {
+    Employees(EmployeeRequest),
+    Departments(DepartmentRequest),
+    DeptEmp(DeptEmpRequest),
+    DeptManager(DeptManagerRequest),
+    Titles(TitleRequest),
+    Salaries(SalaryRequest),
+}
+
+impl BatchOperation {
+    pub fn resource(&self) -> &'static str {
+        match self {
+            BatchOperation::Employees(_) => "employees",
+            BatchOperation::Departments(_) => "departments",
+            BatchOperation::DeptEmp(_) => "dept_emp",
+            BatchOperation::DeptManager(_) => "dept_manager",
+            BatchOperation::Titles(_) => "titles",
+            BatchOperation::Salaries(_) => "salaries",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    // Ran and was committed.
+    Ok,
+    // Ran, but a later operation failed so it was undone.
+    RolledBack,
+    // The operation that failed.
+    Failed,
+    // Not run because an earlier operation failed.
+    Skipped,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchResult {
+    pub committed: bool,
+    // One per operation, in request order.
+    pub results: Vec<OperationResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OperationResult {
+    pub index: usize,
+    pub resource: String,
+    pub action: String,
+    pub status: OperationStatus,
+    // What the action returns on its own endpoint: the row, or the affected-rows message.
+    #[schema(value_type = Object, nullable)]
+    pub result: Option<Value>,
+    pub error: Option<ErrorBody>,
+}
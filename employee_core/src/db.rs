@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use std::time::Duration;
+use tracing::{info, instrument, warn};
+
+use crate::config::{Config, DatabaseConfig};
+
+pub struct AppState {
+    pub db: MySqlPool,
+    pub config: Config,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+#[allow(dead_code)]
+pub struct RateLimit {
+    pub username: String,
+    pub remaining_requests: i32,
+}
+
+// The employees schema marks intervals that are still open with a to_date of 9999-01-01
+pub fn open_end_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(9999, 1, 1).unwrap()
+}
+
+// Intervals in effect on a date, with the date bound to both placeholders: started on or
+// before it and not yet ended (to_date is the first day the row no longer applies).
+pub const IN_EFFECT: &str = "from_date <= ? AND to_date > ?";
+
+// row_version of a freshly inserted row; every later write bumps it by one
+pub fn initial_row_version() -> i32 {
+    1
+}
+
+// Connections are opened on demand, so the server comes up (and /readyz reports it) while the
+// database is still unreachable. Only an unusable URL is an error here.
+pub async fn create_pool(config: &DatabaseConfig) -> Result<MySqlPool, sqlx::Error> {
+    let pool = MySqlPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+        .idle_timeout(config.idle_timeout_secs.map(Duration::from_secs))
+        .connect_lazy(&config.url)?;
+
+    match ping(&pool).await {
+        Ok(()) => info!("Connected to database"),
+        Err(err) => warn!(error = ?err, "Database not reachable yet, will keep retrying"),
+    }
+    Ok(pool)
+}
+
+#[instrument(name = "db.ping", skip_all)]
+pub async fn ping(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT 1").execute(pool).await.map(|_| ())
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use sqlx::FromRow;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::db::initial_row_version;
+use crate::error::ApiError;
+use crate::repository::{BindColumns, CrudAction, MySqlQuery, Resource};
+use crate::validation::{Validate, ValidationErrors};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Department {
+    pub dept_no: String,
+    pub dept_name: String,
+    #[serde(default = "initial_row_version")]
+    pub row_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// One department as of a date: who managed it and who worked in it then. Replaces the
+// show_departments() procedure and v_full_departments view from objects.sql.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DepartmentSummary {
+    pub dept_no: String,
+    pub dept_name: String,
+    pub as_of: NaiveDate,
+    // The manager whose dept_manager interval covers as_of; the latest one if several do.
+    pub manager: Option<DepartmentManagerSummary>,
+    // Employees whose dept_emp interval covers as_of.
+    pub headcount: i64,
+    // Mean of those employees' salaries in effect on as_of, rounded to cents; null without any.
+    pub average_salary: Option<f64>,
+    // Those employees' titles in effect on as_of, most common first.
+    pub titles: Vec<TitleCount>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct DepartmentManagerSummary {
+    pub emp_no: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub from_date: NaiveDate,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TitleCount {
+    pub title: String,
+    pub employees: i64,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SummaryQuery {
+    // Only this department; every department when left out.
+    pub dept_no: Option<String>,
+    // Defaults to today.
+    pub as_of: Option<NaiveDate>,
+}
+
+impl Validate for SummaryQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if let Some(dept_no) = &self.dept_no {
+            errors.max_length("dept_no", dept_no, 4);
+        }
+        errors.into_result()
+    }
+}
+
+// Column limits from employees.sql: dept_no CHAR(4), dept_name VARCHAR(40).
+impl Validate for Department {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.max_length("dept_no", &self.dept_no, 4);
+        errors.max_length("dept_name", &self.dept_name, 40);
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct DepartmentKey {
+    pub dept_no: String,
+}
+
+impl BindColumns for DepartmentKey {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "dept_no" => query.bind(&self.dept_no),
+            _ => unreachable!("DepartmentKey has no column {}", column),
+        }
+    }
+}
+
+impl BindColumns for Department {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "dept_no" => query.bind(&self.dept_no),
+            "dept_name" => query.bind(&self.dept_name),
+            _ => unreachable!("departments has no column {}", column),
+        }
+    }
+}
+
+impl Resource for Department {
+    type Key = DepartmentKey;
+
+    const TABLE: &'static str = "departments";
+    const NAME: &'static str = "Department";
+    const KEY_COLUMNS: &'static [&'static str] = &["dept_no"];
+    const COLUMNS: &'static [&'static str] = &["dept_no", "dept_name"];
+    // The assignment rows ON DELETE CASCADE would take with the department.
+    const CASCADE_TABLES: &'static [&'static str] = &["dept_emp", "dept_manager"];
+
+    fn key(&self) -> DepartmentKey {
+        DepartmentKey { dept_no: self.dept_no.clone() }
+    }
+
+    fn set_key(&mut self, key: DepartmentKey) {
+        self.dept_no = key.dept_no;
+    }
+
+    fn row_version(&self) -> i32 {
+        self.row_version
+    }
+
+    fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.deleted_at
+    }
+}
+
+// Everything POST /departments accepts, selected by the "action" field.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DepartmentRequest {
+    Create { department: Department },
+    ReadAll {
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Read {
+        dept_no: String,
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Update {
+        dept_no: String,
+        version: Option<i32>,
+        department: Department,
+    },
+    Delete { dept_no: String },
+    Restore { dept_no: String },
+    Purge { dept_no: String },
+    Summary {
+        dept_no: Option<String>,
+        as_of: Option<NaiveDate>,
+    },
+}
+
+impl DepartmentRequest {
+    // The generic CRUD action this request maps to; summary has its own handler.
+    pub fn into_action(self) -> Result<CrudAction<Department>, ApiError> {
+        let action = match self {
+            DepartmentRequest::Create { department } => CrudAction::Create(department),
+            DepartmentRequest::ReadAll { include_deleted } => CrudAction::ReadAll { include_deleted },
+            DepartmentRequest::Read { dept_no, include_deleted } => CrudAction::Read { key: DepartmentKey { dept_no }, include_deleted },
+            DepartmentRequest::Update { dept_no, version, department } => CrudAction::Update { key: DepartmentKey { dept_no }, version, row: department },
+            DepartmentRequest::Delete { dept_no } => CrudAction::Delete(DepartmentKey { dept_no }),
+            DepartmentRequest::Restore { dept_no } => CrudAction::Restore(DepartmentKey { dept_no }),
+            DepartmentRequest::Purge { dept_no } => CrudAction::Purge(DepartmentKey { dept_no }),
+            DepartmentRequest::Summary { .. } => return Err(ApiError::Unprocessable("summary is not a CRUD action".to_string())),
+        };
+        Ok(action)
+    }
+}
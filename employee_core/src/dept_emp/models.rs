@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use sqlx::FromRow;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::db::initial_row_version;
+use crate::repository::{BindColumns, CrudAction, MySqlQuery, Resource};
+use crate::validation::{Validate, ValidationErrors};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DeptEmp {
+    pub emp_no: i32,
+    pub dept_no: String,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    #[serde(default = "initial_row_version")]
+    pub row_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl Validate for DeptEmp {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.positive("emp_no", self.emp_no as i64);
+        errors.max_length("dept_no", &self.dept_no, 4);
+        errors.not_before("to_date", self.to_date, "from_date", self.from_date);
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct DeptEmpKey {
+    pub emp_no: i32,
+    pub dept_no: String,
+    pub from_date: NaiveDate,
+}
+
+impl BindColumns for DeptEmpKey {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "dept_no" => query.bind(&self.dept_no),
+            "from_date" => query.bind(self.from_date),
+            _ => unreachable!("DeptEmpKey has no column {}", column),
+        }
+    }
+}
+
+impl BindColumns for DeptEmp {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "dept_no" => query.bind(&self.dept_no),
+            "from_date" => query.bind(self.from_date),
+            "to_date" => query.bind(self.to_date),
+            _ => unreachable!("dept_emp has no column {}", column),
+        }
+    }
+}
+
+impl Resource for DeptEmp {
+    type Key = DeptEmpKey;
+
+    const TABLE: &'static str = "dept_emp";
+    const NAME: &'static str = "DeptEmp";
+    // An employee can have several stints in one department, told apart by from_date.
+    const KEY_COLUMNS: &'static [&'static str] = &["emp_no", "dept_no", "from_date"];
+    const COLUMNS: &'static [&'static str] = &["emp_no", "dept_no", "from_date", "to_date"];
+
+    fn key(&self) -> DeptEmpKey {
+        DeptEmpKey { emp_no: self.emp_no, dept_no: self.dept_no.clone(), from_date: self.from_date }
+    }
+
+    fn set_key(&mut self, key: DeptEmpKey) {
+        self.emp_no = key.emp_no;
+        self.dept_no = key.dept_no;
+        self.from_date = key.from_date;
+    }
+
+    fn row_version(&self) -> i32 {
+        self.row_version
+    }
+
+    fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.deleted_at
+    }
+}
+
+// Everything POST /dept_emp accepts, selected by the "action" field.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeptEmpRequest {
+    Create { dept_emp: DeptEmp },
+    ReadAll {
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Read {
+        emp_no: i32,
+        dept_no: String,
+        from_date: NaiveDate,
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Update {
+        emp_no: i32,
+        dept_no: String,
+        from_date: NaiveDate,
+        version: Option<i32>,
+        dept_emp: DeptEmp,
+    },
+    Delete { emp_no: i32, dept_no: String, from_date: NaiveDate },
+    Restore { emp_no: i32, dept_no: String, from_date: NaiveDate },
+    Purge { emp_no: i32, dept_no: String, from_date: NaiveDate },
+}
+
+impl DeptEmpRequest {
+    // The generic CRUD action this request maps to.
+    pub fn into_action(self) -> CrudAction<DeptEmp> {
+        match self {
+            DeptEmpRequest::Create { dept_emp } => CrudAction::Create(dept_emp),
+            DeptEmpRequest::ReadAll { include_deleted } => CrudAction::ReadAll { include_deleted },
+            DeptEmpRequest::Read { emp_no, dept_no, from_date, include_deleted } => CrudAction::Read { key: DeptEmpKey { emp_no, dept_no, from_date }, include_deleted },
+            DeptEmpRequest::Update { emp_no, dept_no, from_date, version, dept_emp } => CrudAction::Update { key: DeptEmpKey { emp_no, dept_no, from_date }, version, row: dept_emp },
+            DeptEmpRequest::Delete { emp_no, dept_no, from_date } => CrudAction::Delete(DeptEmpKey { emp_no, dept_no, from_date }),
+            DeptEmpRequest::Restore { emp_no, dept_no, from_date } => CrudAction::Restore(DeptEmpKey { emp_no, dept_no, from_date }),
+            DeptEmpRequest::Purge { emp_no, dept_no, from_date } => CrudAction::Purge(DeptEmpKey { emp_no, dept_no, from_date }),
+        }
+    }
+}
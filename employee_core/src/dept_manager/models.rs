@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use sqlx::FromRow;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::db::initial_row_version;
+use crate::repository::{BindColumns, CrudAction, MySqlQuery, Resource};
+use crate::validation::{Validate, ValidationErrors};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DeptManager {
+    pub emp_no: i32,
+    pub dept_no: String,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    #[serde(default = "initial_row_version")]
+    pub row_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl Validate for DeptManager {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.positive("emp_no", self.emp_no as i64);
+        errors.max_length("dept_no", &self.dept_no, 4);
+        errors.not_before("to_date", self.to_date, "from_date", self.from_date);
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct DeptManagerKey {
+    pub emp_no: i32,
+    pub dept_no: String,
+}
+
+impl BindColumns for DeptManagerKey {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "dept_no" => query.bind(&self.dept_no),
+            _ => unreachable!("DeptManagerKey has no column {}", column),
+        }
+    }
+}
+
+impl BindColumns for DeptManager {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "dept_no" => query.bind(&self.dept_no),
+            "from_date" => query.bind(self.from_date),
+            "to_date" => query.bind(self.to_date),
+            _ => unreachable!("dept_manager has no column {}", column),
+        }
+    }
+}
+
+impl Resource for DeptManager {
+    type Key = DeptManagerKey;
+
+    const TABLE: &'static str = "dept_manager";
+    const NAME: &'static str = "DeptManager";
+    const KEY_COLUMNS: &'static [&'static str] = &["emp_no", "dept_no"];
+    const COLUMNS: &'static [&'static str] = &["emp_no", "dept_no", "from_date", "to_date"];
+    fn key(&self) -> DeptManagerKey {
+        DeptManagerKey { emp_no: self.emp_no, dept_no: self.dept_no.clone() }
+    }
+
+    fn set_key(&mut self, key: DeptManagerKey) {
+        self.emp_no = key.emp_no;
+        self.dept_no = key.dept_no;
+    }
+
+    fn row_version(&self) -> i32 {
+        self.row_version
+    }
+
+    fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.deleted_at
+    }
+}
+
+// Everything POST /dept_manager accepts, selected by the "action" field.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeptManagerRequest {
+    Create { dept_manager: DeptManager },
+    ReadAll {
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Read {
+        emp_no: i32,
+        dept_no: String,
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Update {
+        emp_no: i32,
+        dept_no: String,
+        version: Option<i32>,
+        dept_manager: DeptManager,
+    },
+    Delete { emp_no: i32, dept_no: String },
+    Restore { emp_no: i32, dept_no: String },
+    Purge { emp_no: i32, dept_no: String },
+}
+
+impl DeptManagerRequest {
+    // The generic CRUD action this request maps to.
+    pub fn into_action(self) -> CrudAction<DeptManager> {
+        match self {
+            DeptManagerRequest::Create { dept_manager } => CrudAction::Create(dept_manager),
+            DeptManagerRequest::ReadAll { include_deleted } => CrudAction::ReadAll { include_deleted },
+            DeptManagerRequest::Read { emp_no, dept_no, include_deleted } => CrudAction::Read { key: DeptManagerKey { emp_no, dept_no }, include_deleted },
+            DeptManagerRequest::Update { emp_no, dept_no, version, dept_manager } => CrudAction::Update { key: DeptManagerKey { emp_no, dept_no }, version, row: dept_manager },
+            DeptManagerRequest::Delete { emp_no, dept_no } => CrudAction::Delete(DeptManagerKey { emp_no, dept_no }),
+            DeptManagerRequest::Restore { emp_no, dept_no } => CrudAction::Restore(DeptManagerKey { emp_no, dept_no }),
+            DeptManagerRequest::Purge { emp_no, dept_no } => CrudAction::Purge(DeptManagerKey { emp_no, dept_no }),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use sqlx::FromRow;
+
+use crate::db::initial_row_version;
+use crate::error::ApiError;
+use crate::repository::{BindColumns, CrudAction, MySqlQuery, Resource};
+use crate::validation::{Validate, ValidationErrors};
+use crate::dept_emp::models::DeptEmp;
+use crate::salaries::models::Salary;
+use crate::titles::models::Title;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Employee {
+    // Leave out (or send 0) on create to have the server allocate the next number.
+    #[serde(default)]
+    pub emp_no: i32,
+    pub birth_date: NaiveDate,
+    pub first_name: String,
+    pub last_name: String,
+    pub gender: String,
+    pub hire_date: NaiveDate,
+    #[serde(default = "initial_row_version")]
+    pub row_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Termination {
+    pub emp_no: i32,
+    pub termination_date: NaiveDate,
+    pub reason: String,
+    pub rehire_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TerminateEmployee {
+    pub termination_date: NaiveDate,
+    pub reason: String,
+}
+
+// The department, title and salary the employee comes back with.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RehireEmployee {
+    pub rehire_date: NaiveDate,
+    pub dept_no: String,
+    pub title: String,
+    pub salary: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TerminationResult {
+    pub termination: Termination,
+    pub closed_dept_emp: u64,
+    pub closed_dept_manager: u64,
+    pub closed_titles: u64,
+    pub closed_salaries: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RehireResult {
+    pub emp_no: i32,
+    pub rehire_date: NaiveDate,
+    pub dept_emp: DeptEmp,
+    pub title: Title,
+    pub salary: Salary,
+}
+
+// Column limits from employees.sql: first_name VARCHAR(14), last_name VARCHAR(16), gender ENUM('M','F').
+impl Validate for Employee {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if self.emp_no < 0 {
+            errors.add("emp_no", "must not be negative");
+        }
+        errors.max_length("first_name", &self.first_name, 14);
+        errors.max_length("last_name", &self.last_name, 16);
+        if self.gender != "M" && self.gender != "F" {
+            errors.add("gender", "must be 'M' or 'F'");
+        }
+        if self.hire_date <= self.birth_date {
+            errors.add("hire_date", "must be after birth_date");
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for TerminateEmployee {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.max_length("reason", &self.reason, 255);
+        errors.into_result()
+    }
+}
+
+impl Validate for RehireEmployee {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.max_length("dept_no", &self.dept_no, 4);
+        errors.max_length("title", &self.title, 50);
+        errors.positive("salary", self.salary as i64);
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct EmployeeKey {
+    pub emp_no: i32,
+}
+
+impl BindColumns for EmployeeKey {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            _ => unreachable!("EmployeeKey has no column {}", column),
+        }
+    }
+}
+
+impl BindColumns for Employee {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            // NULL lets AUTO_INCREMENT pick the number.
+            "emp_no" => query.bind((self.emp_no != 0).then_some(self.emp_no)),
+            "birth_date" => query.bind(self.birth_date),
+            "first_name" => query.bind(&self.first_name),
+            "last_name" => query.bind(&self.last_name),
+            "gender" => query.bind(&self.gender),
+            "hire_date" => query.bind(self.hire_date),
+            _ => unreachable!("employees has no column {}", column),
+        }
+    }
+}
+
+impl Resource for Employee {
+    type Key = EmployeeKey;
+
+    const TABLE: &'static str = "employees";
+    const NAME: &'static str = "Employee";
+    const KEY_COLUMNS: &'static [&'static str] = &["emp_no"];
+    const COLUMNS: &'static [&'static str] = &["emp_no", "birth_date", "first_name", "last_name", "gender", "hire_date"];
+    // The history rows ON DELETE CASCADE would take with the employee.
+    const CASCADE_TABLES: &'static [&'static str] = &["dept_emp", "dept_manager", "titles", "salaries"];
+
+    fn key(&self) -> EmployeeKey {
+        EmployeeKey { emp_no: self.emp_no }
+    }
+
+    fn set_key(&mut self, key: EmployeeKey) {
+        self.emp_no = key.emp_no;
+    }
+
+    fn row_version(&self) -> i32 {
+        self.row_version
+    }
+
+    fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.deleted_at
+    }
+
+    fn generates_key(&self) -> bool {
+        self.emp_no == 0
+    }
+
+    fn set_generated_key(&mut self, id: u64) -> Result<(), ApiError> {
+        self.emp_no = i32::try_from(id).map_err(|_| ApiError::internal(format!("allocated emp_no {} does not fit an INT", id)))?;
+        Ok(())
+    }
+}
+
+// Everything POST /employees accepts, selected by the "action" field.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum EmployeeRequest {
+    Create { employee: Employee },
+    ReadAll {
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Read {
+        emp_no: i32,
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Update {
+        emp_no: i32,
+        version: Option<i32>,
+        employee: Employee,
+    },
+    Delete { emp_no: i32 },
+    Restore { emp_no: i32 },
+    Purge { emp_no: i32 },
+    Terminate { emp_no: i32, termination: TerminateEmployee },
+    Rehire { emp_no: i32, rehire: RehireEmployee },
+}
+
+impl EmployeeRequest {
+    // The generic CRUD action this request maps to; terminate and rehire have their own handlers.
+    pub fn into_action(self) -> Result<CrudAction<Employee>, ApiError> {
+        let action = match self {
+            EmployeeRequest::Create { employee } => CrudAction::Create(employee),
+            EmployeeRequest::ReadAll { include_deleted } => CrudAction::ReadAll { include_deleted },
+            EmployeeRequest::Read { emp_no, include_deleted } => CrudAction::Read { key: EmployeeKey { emp_no }, include_deleted },
+            EmployeeRequest::Update { emp_no, version, employee } => CrudAction::Update { key: EmployeeKey { emp_no }, version, row: employee },
+            EmployeeRequest::Delete { emp_no } => CrudAction::Delete(EmployeeKey { emp_no }),
+            EmployeeRequest::Restore { emp_no } => CrudAction::Restore(EmployeeKey { emp_no }),
+            EmployeeRequest::Purge { emp_no } => CrudAction::Purge(EmployeeKey { emp_no }),
+            EmployeeRequest::Terminate { .. } | EmployeeRequest::Rehire { .. } => {
+                return Err(ApiError::Unprocessable("terminate and rehire are not CRUD actions".to_string()));
+            }
+        };
+        Ok(action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee() -> Employee {
+        Employee {
+            emp_no: 10001,
+            birth_date: NaiveDate::from_ymd_opt(1953, 9, 2).unwrap(),
+            first_name: "Georgi".to_string(),
+            last_name: "Facello".to_string(),
+            gender: "M".to_string(),
+            hire_date: NaiveDate::from_ymd_opt(1986, 6, 26).unwrap(),
+            row_version: initial_row_version(),
+            deleted_at: None,
+        }
+    }
+
+    fn fields(employee: &Employee) -> Vec<String> {
+        employee.validate().err().map(|errors| errors.errors.into_iter().map(|error| error.field).collect()).unwrap_or_default()
+    }
+
+    #[test]
+    fn a_valid_employee_passes() {
+        assert!(fields(&employee()).is_empty());
+    }
+
+    #[test]
+    fn emp_no_may_be_left_for_allocation_but_not_negative() {
+        assert!(fields(&Employee { emp_no: 0, ..employee() }).is_empty());
+        let errors = Employee { emp_no: -1, ..employee() }.validate().unwrap_err();
+        assert_eq!((errors.errors[0].field.as_str(), errors.errors[0].message.as_str()), ("emp_no", "must not be negative"));
+    }
+
+    #[test]
+    fn gender_must_be_m_or_f() {
+        assert_eq!(fields(&Employee { gender: "X".to_string(), ..employee() }), ["gender"]);
+        assert_eq!(fields(&Employee { gender: "m".to_string(), ..employee() }), ["gender"]);
+        assert!(fields(&Employee { gender: "F".to_string(), ..employee() }).is_empty());
+    }
+
+    #[test]
+    fn hire_date_must_be_after_birth_date() {
+        let birth_date = employee().birth_date;
+        assert_eq!(fields(&Employee { hire_date: birth_date, ..employee() }), ["hire_date"]);
+    }
+
+    #[test]
+    fn names_must_fit_their_columns() {
+        let long = Employee { first_name: "A".repeat(15), last_name: "B".repeat(17), ..employee() };
+        assert_eq!(fields(&long), ["first_name", "last_name"]);
+        let fits = Employee { first_name: "A".repeat(14), last_name: "B".repeat(16), ..employee() };
+        assert!(fields(&fits).is_empty());
+        assert_eq!(fields(&Employee { first_name: " ".to_string(), ..employee() }), ["first_name"]);
+    }
+
+    #[test]
+    fn rehire_needs_a_department_title_and_positive_salary() {
+        let rehire = RehireEmployee {
+            rehire_date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            dept_no: "d00005".to_string(),
+            title: String::new(),
+            salary: 0,
+        };
+        let errors = rehire.validate().unwrap_err().errors.into_iter().map(|error| error.field).collect::<Vec<_>>();
+        assert_eq!(errors, ["dept_no", "title", "salary"]);
+    }
+}
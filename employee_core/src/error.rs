@@ -0,0 +1,207 @@
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::{header::ETAG, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::mysql::MySqlDatabaseError;
+use std::fmt::Display;
+use tracing::error;
+use utoipa::ToSchema;
+
+use crate::request_id::current_request_id;
+use crate::validation::ValidationErrors;
+
+// Every failure the API can report. Each variant renders as the same JSON envelope:
+// {"code": "...", "message": "...", "details": ..., "request_id": "..."}
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    // Stale row_version: 412 for If-Match, 409 for a body version, with the current row in details.
+    VersionConflict { status: StatusCode, current_version: i32, details: Value },
+    PreconditionRequired(String),
+    Validation(ValidationErrors),
+    Unprocessable(String),
+    TooManyRequests(String),
+    Unavailable(String),
+    Internal(String),
+}
+
+// The JSON body of every error response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    // One of the codes returned by ApiError::code, e.g. "validation_failed"
+    pub code: String,
+    pub message: String,
+    // Field errors for validation_failed, the current row for version_conflict, otherwise null
+    #[schema(value_type = Object, nullable)]
+    pub details: Value,
+    pub request_id: Option<String>,
+}
+
+impl ApiError {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        ApiError::BadRequest(message.into())
+    }
+
+    pub fn missing_field(field: &str) -> Self {
+        ApiError::BadRequest(format!("missing or invalid field: {}", field))
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        ApiError::NotFound(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        ApiError::Conflict(message.into())
+    }
+
+    // Logs the real cause and hides it from the client.
+    pub fn internal(cause: impl Display) -> Self {
+        error!(cause = %cause, "Internal error");
+        ApiError::Internal("Internal server error".to_string())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::VersionConflict { status, .. } => *status,
+            ApiError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            ApiError::Validation(_) | ApiError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    // Stable, machine-readable identifier clients can switch on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::VersionConflict { .. } => "version_conflict",
+            ApiError::PreconditionRequired(_) => "precondition_required",
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::Unprocessable(_) => "unprocessable",
+            ApiError::TooManyRequests(_) => "rate_limited",
+            ApiError::Unavailable(_) => "unavailable",
+            ApiError::Internal(_) => "internal_error",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            ApiError::VersionConflict { .. } => "Version conflict".to_string(),
+            ApiError::Validation(_) => "Validation failed".to_string(),
+            ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Forbidden(message)
+            | ApiError::NotFound(message)
+            | ApiError::Conflict(message)
+            | ApiError::PreconditionRequired(message)
+            | ApiError::Unprocessable(message)
+            | ApiError::TooManyRequests(message)
+            | ApiError::Unavailable(message)
+            | ApiError::Internal(message) => message.clone(),
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            ApiError::VersionConflict { details, .. } => details.clone(),
+            ApiError::Validation(errors) => json!(errors.errors),
+            _ => Value::Null,
+        }
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+impl From<&ApiError> for ErrorBody {
+    fn from(error: &ApiError) -> Self {
+        ErrorBody {
+            code: error.code().to_string(),
+            message: error.message(),
+            details: error.details(),
+            request_id: current_request_id(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut response = (self.status(), Json(ErrorBody::from(&self))).into_response();
+        if let ApiError::VersionConflict { current_version, .. } = self
+            && let Ok(value) = HeaderValue::from_str(&format!("\"{}\"", current_version))
+        {
+            response.headers_mut().insert(ETAG, value);
+        }
+        response
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        ApiError::Validation(errors)
+    }
+}
+
+// Extractor failures on the REST routes (bad path segment, query string or body) use the
+// same envelope as everything else instead of axum's plain-text rejections.
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+// MySQL server error numbers we can explain to the client.
+const ER_DUP_ENTRY: u16 = 1062;
+const ER_ROW_IS_REFERENCED: u16 = 1451;
+const ER_NO_REFERENCED_ROW: u16 = 1452;
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => ApiError::not_found("Row not found"),
+            sqlx::Error::PoolTimedOut => ApiError::Unavailable("Database is busy, try again later".to_string()),
+            sqlx::Error::Database(db_error) => {
+                match db_error.try_downcast_ref::<MySqlDatabaseError>().map(|e| e.number()) {
+                    Some(ER_DUP_ENTRY) => ApiError::conflict("A row with this key already exists"),
+                    Some(ER_ROW_IS_REFERENCED) => ApiError::conflict("Row is still referenced by other rows"),
+                    Some(ER_NO_REFERENCED_ROW) => ApiError::Unprocessable("Referenced row does not exist".to_string()),
+                    _ => ApiError::internal(&error),
+                }
+            }
+            _ => ApiError::internal(&error),
+        }
+    }
+}
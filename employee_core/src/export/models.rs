@@ -0,0 +1,199 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::repository::{FilterValue, Resource, RowFilter};
+use crate::validation::{Validate, ValidationErrors};
+
+// The tables that can be exported, as they appear in the /export path.
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportResource {
+    Employees,
+    Departments,
+    DeptEmp,
+    DeptManager,
+    Titles,
+    Salaries,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Ndjson,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+// Filters for an export. Every filter is optional; without any, the whole table is exported.
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    // Only the rows in effect on this date: history rows whose interval covers it, employees
+    // hired by then and not terminated.
+    pub as_of: Option<NaiveDate>,
+    pub emp_no: Option<i32>,
+    // An inclusive emp_no range, for splitting a large extract across several requests.
+    pub min_emp_no: Option<i32>,
+    pub max_emp_no: Option<i32>,
+    // Rows of employees in this department (on as_of, when given); departments, dept_emp
+    // and dept_manager filter on their own dept_no column.
+    pub dept_no: Option<String>,
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+impl Validate for ExportQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        for (field, value) in [("emp_no", self.emp_no), ("min_emp_no", self.min_emp_no), ("max_emp_no", self.max_emp_no)] {
+            if let Some(value) = value {
+                errors.positive(field, value as i64);
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_emp_no, self.max_emp_no) && max < min {
+            errors.add("max_emp_no", "must not be less than min_emp_no");
+        }
+        if let Some(dept_no) = &self.dept_no {
+            errors.max_length("dept_no", dept_no, 4);
+        }
+        errors.into_result()
+    }
+}
+
+impl ExportQuery {
+    // The filters as SQL conditions on R's table. Which filters apply depends on the columns
+    // the table has; one that cannot apply is a validation error rather than silently ignored.
+    pub fn row_filter<R: Resource>(&self) -> Result<RowFilter, ValidationErrors> {
+        let has = |column: &str| R::COLUMNS.contains(&column);
+        let mut filter = RowFilter::new(self.include_deleted);
+        let mut errors = ValidationErrors::default();
+
+        if self.emp_no.is_some() || self.min_emp_no.is_some() || self.max_emp_no.is_some() {
+            if has("emp_no") {
+                if let Some(emp_no) = self.emp_no {
+                    filter.condition("emp_no = ?", [FilterValue::Int(emp_no)]);
+                }
+                if let Some(min) = self.min_emp_no {
+                    filter.condition("emp_no >= ?", [FilterValue::Int(min)]);
+                }
+                if let Some(max) = self.max_emp_no {
+                    filter.condition("emp_no <= ?", [FilterValue::Int(max)]);
+                }
+            } else {
+                errors.add("emp_no", format!("{} has no emp_no column", R::TABLE));
+            }
+        }
+
+        if let Some(dept_no) = &self.dept_no {
+            if has("dept_no") {
+                filter.condition("dept_no = ?", [FilterValue::Text(dept_no.clone())]);
+            } else {
+                let mut values = vec![FilterValue::Text(dept_no.clone())];
+                let period = match self.as_of {
+                    Some(as_of) => {
+                        values.extend([FilterValue::Date(as_of), FilterValue::Date(as_of)]);
+                        " AND from_date <= ? AND to_date > ?"
+                    }
+                    None => "",
+                };
+                filter.condition(
+                    format!("emp_no IN (SELECT emp_no FROM dept_emp WHERE dept_no = ? AND deleted_at IS NULL{})", period),
+                    values,
+                );
+            }
+        }
+
+        if let Some(as_of) = self.as_of {
+            if has("from_date") {
+                // Intervals end on to_date exclusive; an open title has no to_date at all.
+                filter.condition("from_date <= ? AND (to_date IS NULL OR to_date > ?)", [FilterValue::Date(as_of), FilterValue::Date(as_of)]);
+            } else if has("hire_date") {
+                filter.condition(
+                    "hire_date <= ? AND NOT EXISTS (SELECT 1 FROM terminations t WHERE t.emp_no = employees.emp_no \
+                        AND t.termination_date <= ? AND (t.rehire_date IS NULL OR t.rehire_date > ?))",
+                    [FilterValue::Date(as_of), FilterValue::Date(as_of), FilterValue::Date(as_of)],
+                );
+            } else {
+                errors.add("as_of", format!("{} has no dates to filter on", R::TABLE));
+            }
+        }
+
+        errors.into_result().map(|_| filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::departments::models::Department;
+    use crate::employees::models::Employee;
+    use crate::salaries::models::Salary;
+
+    fn fields(errors: ValidationErrors) -> Vec<String> {
+        errors.errors.into_iter().map(|error| error.field).collect()
+    }
+
+    #[test]
+    fn validate_rejects_an_inverted_emp_no_range() {
+        let query = ExportQuery { min_emp_no: Some(20), max_emp_no: Some(10), ..Default::default() };
+        assert_eq!(fields(query.validate().unwrap_err()), ["max_emp_no"]);
+
+        let query = ExportQuery { min_emp_no: Some(10), max_emp_no: Some(10), ..Default::default() };
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_checks_emp_no_and_dept_no() {
+        let query = ExportQuery { emp_no: Some(0), dept_no: Some("d00001".to_string()), ..Default::default() };
+        assert_eq!(fields(query.validate().unwrap_err()), ["emp_no", "dept_no"]);
+    }
+
+    #[test]
+    fn row_filter_without_filters_selects_the_whole_table() {
+        let filter = ExportQuery::default().row_filter::<Salary>().unwrap();
+        assert_eq!(filter.select_sql::<Salary>(), "SELECT * FROM salaries WHERE (deleted_at IS NULL OR ?) ORDER BY emp_no, from_date");
+    }
+
+    #[test]
+    fn row_filter_uses_the_interval_of_history_tables() {
+        let query = ExportQuery { as_of: NaiveDate::from_ymd_opt(2000, 1, 1), min_emp_no: Some(10001), ..Default::default() };
+        let sql = query.row_filter::<Salary>().unwrap().select_sql::<Salary>();
+        assert!(sql.contains(" AND (emp_no >= ?) AND (from_date <= ? AND (to_date IS NULL OR to_date > ?))"), "{}", sql);
+    }
+
+    #[test]
+    fn row_filter_uses_hire_date_and_dept_emp_for_employees() {
+        let query = ExportQuery { as_of: NaiveDate::from_ymd_opt(2000, 1, 1), dept_no: Some("d005".to_string()), ..Default::default() };
+        let sql = query.row_filter::<Employee>().unwrap().select_sql::<Employee>();
+        assert!(sql.contains("emp_no IN (SELECT emp_no FROM dept_emp WHERE dept_no = ? AND deleted_at IS NULL AND from_date <= ? AND to_date > ?)"), "{}", sql);
+        assert!(sql.contains("hire_date <= ? AND NOT EXISTS"), "{}", sql);
+    }
+
+    #[test]
+    fn row_filter_rejects_filters_the_table_cannot_apply() {
+        let query = ExportQuery { emp_no: Some(10001), as_of: NaiveDate::from_ymd_opt(2000, 1, 1), ..Default::default() };
+        assert_eq!(fields(query.row_filter::<Department>().unwrap_err()), ["emp_no", "as_of"]);
+    }
+}
@@ -0,0 +1,168 @@
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{FromRequestParts, State},
+    http::{header::{CONTENT_TYPE, ETAG}, request::Parts, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sodiumoxide::crypto::hash::sha256;
+use sqlx::{query, query_as, FromRow};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::auth::Claims;
+use crate::db::AppState;
+use crate::error::ApiError;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+// Set to "true" on a response that was replayed from an earlier request.
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LENGTH: usize = 255;
+// The largest body any write route accepts (CSV imports).
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+
+// A write (POST, PUT, PATCH or DELETE) sent with an Idempotency-Key runs once per user and key
+// within idempotency.window_secs; repeats get the first response back without running again.
+// 5xx responses are not kept, so a retry after a server-side failure does run again.
+pub async fn idempotency_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let is_write = matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH | Method::DELETE);
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER).filter(|_| is_write) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key.to_str().ok()
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LENGTH)
+        .ok_or_else(|| ApiError::bad_request(format!("Idempotency-Key must be 1 to {} visible ASCII characters", MAX_KEY_LENGTH)))?
+        .to_string();
+
+    let (mut parts, body) = request.into_parts();
+    let claims = Claims::from_request_parts(&mut parts, &state).await?;
+    let body = to_bytes(body, MAX_BODY_BYTES).await.map_err(|_| ApiError::bad_request("Request body is too large"))?;
+    let request_hash = request_hash(&parts, &body);
+
+    if let Some(stored) = reserve(&state, &claims.sub, &key, &request_hash).await? {
+        return stored.replay(&request_hash);
+    }
+
+    let (parts, body) = next.run(Request::from_parts(parts, Body::from(body))).await.into_parts();
+    if parts.status.is_server_error() {
+        release(&state, &claims.sub, &key).await;
+        return Ok(Response::from_parts(parts, body));
+    }
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            release(&state, &claims.sub, &key).await;
+            return Err(ApiError::internal(e));
+        }
+    };
+    if let Err(e) = complete(&state, &claims.sub, &key, &parts, &body).await {
+        // The write itself went through; only its replay is lost.
+        warn!(error = %e, "Could not store the response for an Idempotency-Key");
+        release(&state, &claims.sub, &key).await;
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+// Method, path with query, and body: reusing a key for anything else is a client bug.
+fn request_hash(parts: &Parts, body: &Bytes) -> String {
+    let mut input = format!("{} {}\n", parts.method, parts.uri).into_bytes();
+    input.extend_from_slice(body);
+    sha256::hash(&input).0.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[derive(FromRow)]
+struct StoredResponse {
+    request_hash: String,
+    status_code: Option<i16>,
+    content_type: Option<String>,
+    etag: Option<String>,
+    response_body: Option<Vec<u8>>,
+}
+
+impl StoredResponse {
+    fn replay(self, request_hash: &str) -> Result<Response, ApiError> {
+        if self.request_hash != request_hash {
+            return Err(ApiError::Unprocessable("Idempotency-Key was already used for a different request".to_string()));
+        }
+        let Some(status_code) = self.status_code else {
+            return Err(ApiError::conflict("A request with this Idempotency-Key is still being processed"));
+        };
+        let status = u16::try_from(status_code).ok().and_then(|code| StatusCode::from_u16(code).ok())
+            .ok_or_else(|| ApiError::internal(format!("stored status code {} is not valid", status_code)))?;
+
+        let mut response = (status, self.response_body.unwrap_or_default()).into_response();
+        let headers = response.headers_mut();
+        headers.remove(CONTENT_TYPE);
+        for (name, value) in [(CONTENT_TYPE, self.content_type), (ETAG, self.etag)] {
+            if let Some(value) = value.and_then(|value| HeaderValue::from_str(&value).ok()) {
+                headers.insert(name, value);
+            }
+        }
+        headers.insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        info!(status = status.as_u16(), "Replayed response for Idempotency-Key");
+        Ok(response)
+    }
+}
+
+// Claims the key for this request, or returns what an earlier request with it left behind.
+// Expired keys are cleared first, so a key can be reused once its window has passed.
+#[instrument(name = "db.idempotency_reserve", skip_all)]
+async fn reserve(state: &AppState, actor: &str, key: &str, request_hash: &str) -> Result<Option<StoredResponse>, ApiError> {
+    query("DELETE FROM idempotency_keys WHERE created_at < NOW() - INTERVAL ? SECOND")
+        .bind(state.config.idempotency.window_secs)
+        .execute(&state.db)
+        .await?;
+
+    let inserted = query("INSERT IGNORE INTO idempotency_keys (actor, idempotency_key, request_hash) VALUES (?, ?, ?)")
+        .bind(actor)
+        .bind(key)
+        .bind(request_hash)
+        .execute(&state.db)
+        .await?;
+    if inserted.rows_affected() == 1 {
+        return Ok(None);
+    }
+
+    let stored = query_as::<_, StoredResponse>(
+        "SELECT request_hash, status_code, content_type, etag, response_body FROM idempotency_keys WHERE actor = ? AND idempotency_key = ?",
+    )
+    .bind(actor)
+    .bind(key)
+    .fetch_optional(&state.db)
+    .await?;
+    // Gone again already: it expired between the two statements.
+    stored.map(Some).ok_or_else(|| ApiError::conflict("Idempotency-Key expired while being checked, retry the request"))
+}
+
+#[instrument(name = "db.idempotency_complete", skip_all)]
+async fn complete(state: &AppState, actor: &str, key: &str, parts: &axum::http::response::Parts, body: &Bytes) -> Result<(), sqlx::Error> {
+    let header = |name| parts.headers.get(name).and_then(|value: &HeaderValue| value.to_str().ok()).map(str::to_string);
+    query("UPDATE idempotency_keys SET status_code = ?, content_type = ?, etag = ?, response_body = ? WHERE actor = ? AND idempotency_key = ?")
+        .bind(parts.status.as_u16() as i16)
+        .bind(header(CONTENT_TYPE))
+        .bind(header(ETAG))
+        .bind(body.as_ref())
+        .bind(actor)
+        .bind(key)
+        .execute(&state.db)
+        .await
+        .map(|_| ())
+}
+
+// Frees the key after a failure that a retry should be allowed to repeat.
+#[instrument(name = "db.idempotency_release", skip_all)]
+async fn release(state: &AppState, actor: &str, key: &str) {
+    let result = query("DELETE FROM idempotency_keys WHERE actor = ? AND idempotency_key = ?")
+        .bind(actor)
+        .bind(key)
+        .execute(&state.db)
+        .await;
+    if let Err(e) = result {
+        warn!(error = %e, "Could not release an Idempotency-Key; retries will see it as in progress until it expires");
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::validation::FieldError;
+
+// The tables HR files can be imported into, as they appear in the /import path.
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportResource {
+    Employees,
+    Salaries,
+    Titles,
+    DeptEmp,
+}
+
+impl FromStr for ImportResource {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "employees" => Ok(ImportResource::Employees),
+            "salaries" => Ok(ImportResource::Salaries),
+            "titles" => Ok(ImportResource::Titles),
+            "dept_emp" => Ok(ImportResource::DeptEmp),
+            other => Err(format!("cannot import {:?}, expected employees, salaries, titles or dept_emp", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    // Any bad row and nothing is written.
+    #[default]
+    AllOrNothing,
+    // Good rows are written, a batch at a time; bad ones are only reported.
+    BestEffort,
+}
+
+impl FromStr for ImportMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "all_or_nothing" => Ok(ImportMode::AllOrNothing),
+            "best_effort" => Ok(ImportMode::BestEffort),
+            other => Err(format!("unknown mode {:?}, expected \"all_or_nothing\" or \"best_effort\"", other)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub mode: ImportMode,
+    // Run every check, inserts included, then roll back.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// What happened to one file. Rows are counted from the CSV's data rows; errors name the
+// file line (the header is line 1) so they can be found in the spreadsheet.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReport {
+    pub resource: String,
+    pub mode: ImportMode,
+    pub dry_run: bool,
+    // Whether the accepted rows were written. False on a dry run, and in all-or-nothing mode
+    // whenever any row failed.
+    pub committed: bool,
+    pub total_rows: usize,
+    // Rows that passed validation and the database; 0 when an all-or-nothing import was
+    // rolled back, since none of them were written.
+    pub accepted_rows: usize,
+    pub failed_rows: usize,
+    pub errors: Vec<RowError>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RowError {
+    pub line: u64,
+    // The row's key, when the row parsed far enough to have one.
+    #[schema(value_type = Object, nullable)]
+    pub key: Option<Value>,
+    pub errors: Vec<FieldError>,
+}
@@ -0,0 +1,25 @@
+// Shared by every server binary: models and their repository, auth, crypto, and the
+// request plumbing (errors, validation, versioning, request ids, audit log).
+pub mod audit;
+pub mod auth;
+pub mod batch;
+pub mod config;
+pub mod db;
+pub mod departments;
+pub mod dept_emp;
+pub mod dept_manager;
+pub mod employees;
+pub mod error;
+pub mod export;
+pub mod idempotency;
+pub mod import;
+pub mod metrics;
+pub mod org;
+pub mod repository;
+pub mod request_id;
+pub mod salaries;
+pub mod sodium;
+pub mod telemetry;
+pub mod titles;
+pub mod validation;
+pub mod versioning;
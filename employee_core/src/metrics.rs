@@ -0,0 +1,118 @@
+use axum::{
+    extract::MatchedPath,
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use sqlx::MySqlPool;
+use std::{cell::RefCell, sync::LazyLock, time::Instant};
+
+// Everything /metrics exposes lives in this registry.
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+static REQUEST_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new("http_request_duration_seconds", "Time to answer a request, by route, action and status"),
+        &["method", "route", "action", "status"],
+    ))
+});
+
+static AUTH_REJECTIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new("auth_rejections_total", "Requests refused by the Claims extractor (401) or a role check (403)"),
+        &["status", "reason"],
+    ))
+});
+
+static RATE_LIMITED: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new("rate_limit_rejections_total", "Requests answered 429 by the rate limiter"))
+});
+
+static DB_POOL_CONNECTIONS: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register(IntGaugeVec::new(
+        Opts::new("db_pool_connections", "sqlx pool connections: idle, in_use, and the configured max"),
+        &["state"],
+    ))
+});
+
+static ENCRYPT_DURATION: LazyLock<Histogram> = LazyLock::new(|| {
+    register(Histogram::with_opts(
+        HistogramOpts::new("encrypt_json_duration_seconds", "Time spent sealing response bodies")
+            .buckets(vec![0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1]),
+    ))
+});
+
+fn register<M: prometheus::core::Collector + Clone + 'static>(metric: Result<M, prometheus::Error>) -> M {
+    let metric = metric.expect("metric definition is valid");
+    REGISTRY.register(Box::new(metric.clone())).expect("metric is registered once");
+    metric
+}
+
+tokio::task_local! {
+    // The "action" of the request being handled, set once its payload parsed.
+    static CURRENT_ACTION: RefCell<Option<String>>;
+}
+
+// Labels the current request with its action. Only called with tags that deserialized
+// into a request enum, so the label set stays bounded.
+pub fn record_action(action: &str) {
+    let _ = CURRENT_ACTION.try_with(|current| *current.borrow_mut() = Some(action.to_string()));
+}
+
+// Times every request. Runs outside the rate limiter so 401s and 429s are counted too.
+pub async fn metrics_middleware(request: Request<axum::body::Body>, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request.extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+
+    let (response, action) = CURRENT_ACTION
+        .scope(RefCell::new(None), async {
+            let response = next.run(request).await;
+            (response, CURRENT_ACTION.with(|action| action.borrow().clone()))
+        })
+        .await;
+
+    REQUEST_DURATION
+        .with_label_values(&[&method, &route, action.as_deref().unwrap_or(""), response.status().as_str()])
+        .observe(started.elapsed().as_secs_f64());
+    response
+}
+
+pub fn auth_rejected(status: u16, reason: &str) {
+    AUTH_REJECTIONS.with_label_values(&[&status.to_string(), reason]).inc();
+}
+
+pub fn rate_limited() {
+    RATE_LIMITED.inc();
+}
+
+pub fn observe_encrypt(started: Instant) {
+    ENCRYPT_DURATION.observe(started.elapsed().as_secs_f64());
+}
+
+// The pool gauges are read at scrape time rather than tracked on every acquire.
+pub fn render(pool: &MySqlPool, max_connections: u32) -> Result<String, String> {
+    // Metrics register on first use; force them so every series is present from the first scrape.
+    LazyLock::force(&REQUEST_DURATION);
+    LazyLock::force(&AUTH_REJECTIONS);
+    LazyLock::force(&RATE_LIMITED);
+    LazyLock::force(&ENCRYPT_DURATION);
+
+    let size = i64::from(pool.size());
+    let idle = pool.num_idle() as i64;
+    DB_POOL_CONNECTIONS.with_label_values(&["idle"]).set(idle);
+    DB_POOL_CONNECTIONS.with_label_values(&["in_use"]).set(size - idle);
+    DB_POOL_CONNECTIONS.with_label_values(&["max"]).set(i64::from(max_connections));
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(buffer).map_err(|e| e.to_string())
+}
@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::validation::{Validate, ValidationErrors};
+
+// Reporting lines come from the schema as it is: an employee reports to the manager of each
+// department they are in, and a department's manager reports to nobody above them.
+
+// Someone on the org chart, with the title they held on the date asked about.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct OrgPerson {
+    pub emp_no: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub title: Option<String>,
+}
+
+// GET /org/employees/{emp_no}/manager
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReportingLine {
+    pub employee: OrgPerson,
+    pub as_of: NaiveDate,
+    // One entry per department the employee was in on as_of.
+    pub managers: Vec<DepartmentManager>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DepartmentManager {
+    pub dept_no: String,
+    pub dept_name: String,
+    // Null when the department had no manager then, or when the employee is that manager.
+    pub manager: Option<OrgPerson>,
+}
+
+// GET /org/employees/{emp_no}/reports
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DirectReports {
+    pub manager: OrgPerson,
+    pub as_of: NaiveDate,
+    // One entry per department they managed on as_of; empty for everyone else.
+    pub departments: Vec<DepartmentReports>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DepartmentReports {
+    pub dept_no: String,
+    pub dept_name: String,
+    pub reports: Vec<OrgPerson>,
+}
+
+// GET /org/tree: the company, its departments, their managers and the people reporting to them.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrgTree {
+    pub as_of: NaiveDate,
+    pub departments: Vec<OrgDepartment>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrgDepartment {
+    pub dept_no: String,
+    pub dept_name: String,
+    pub manager: Option<OrgPerson>,
+    // Everyone else in the department on as_of, in emp_no order.
+    pub employees: Vec<OrgPerson>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OrgQuery {
+    // Defaults to today.
+    pub as_of: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgTreeFormat {
+    #[default]
+    Json,
+    // Graphviz, e.g. `dot -Tsvg org.dot -o org.svg`
+    Dot,
+}
+
+impl OrgTreeFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            OrgTreeFormat::Json => "application/json",
+            OrgTreeFormat::Dot => "text/vnd.graphviz; charset=utf-8",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            OrgTreeFormat::Json => "json",
+            OrgTreeFormat::Dot => "dot",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OrgTreeQuery {
+    #[serde(default)]
+    pub format: OrgTreeFormat,
+    // Defaults to today.
+    pub as_of: Option<NaiveDate>,
+    // Only this department's branch; the whole company when left out.
+    pub dept_no: Option<String>,
+}
+
+impl Validate for OrgTreeQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if let Some(dept_no) = &self.dept_no {
+            errors.max_length("dept_no", dept_no, 4);
+        }
+        errors.into_result()
+    }
+}
@@ -0,0 +1,63 @@
+use serde_json::json;
+use sqlx::{query, MySql, Transaction};
+use tracing::{instrument, warn};
+
+use crate::audit::AuditContext;
+use crate::error::ApiError;
+use crate::repository::resource::*;
+
+// Rows per INSERT statement; best-effort imports also commit once per batch.
+pub const BATCH_ROWS: usize = 500;
+
+// A row from an import file, with the file line it came from.
+pub struct BulkRow<R> {
+    pub line: u64,
+    pub row: R,
+}
+
+// Inserts already validated rows inside the caller's transaction, with one audit entry each.
+// The batch goes in as a single INSERT; if MySQL rejects it (InnoDB undoes just the failed
+// statement), the rows are retried one at a time so each failure is pinned to its line.
+// Returns those per-row failures; anything that is not a row problem ends the import.
+#[instrument(name = "db.insert_batch", skip_all, fields(table = R::TABLE, rows = rows.len()))]
+pub async fn insert_batch<R: Resource>(
+    tx: &mut Transaction<'_, MySql>,
+    audit: &AuditContext,
+    rows: &[BulkRow<R>],
+) -> Result<Vec<(u64, ApiError)>, ApiError> {
+    let query_str = insert_many_sql::<R>(rows.len());
+    let mut insert = query(&query_str);
+    for bulk in rows {
+        for column in R::COLUMNS {
+            insert = bulk.row.bind_column(column, insert);
+        }
+    }
+
+    let mut failures = Vec::new();
+    match insert.execute(&mut **tx).await {
+        Ok(_) => {
+            for bulk in rows {
+                audit.record(tx, "import", R::TABLE, json!(bulk.row.key()), None, Some(&bulk.row)).await?;
+            }
+        }
+        Err(sqlx::Error::Database(_)) => {
+            let query_str = insert_sql::<R>();
+            for bulk in rows {
+                let mut insert = query(&query_str);
+                for column in R::COLUMNS {
+                    insert = bulk.row.bind_column(column, insert);
+                }
+                match insert.execute(&mut **tx).await {
+                    Ok(_) => audit.record(tx, "import", R::TABLE, json!(bulk.row.key()), None, Some(&bulk.row)).await?,
+                    Err(e @ sqlx::Error::Database(_)) => {
+                        warn!(line = bulk.line, error = ?e, "Import row rejected by {}", R::TABLE);
+                        failures.push((bulk.line, e.into()));
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        Err(e) => return Err(e.into()),
+    }
+    Ok(failures)
+}
@@ -0,0 +1,381 @@
+use axum::{
+    extract::{FromRequestParts, Json},
+    http::{request::Parts, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDateTime;
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::{json, to_value};
+use sqlx::{mysql::MySqlRow, query, query_scalar, MySql, Transaction};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{error, info_span, instrument, Instrument};
+
+use crate::audit::AuditContext;
+use crate::auth::Claims;
+use crate::db::AppState;
+use crate::error::ApiError;
+use crate::repository::filter::RowFilter;
+use crate::repository::resource::*;
+use crate::request_id::RequestId;
+use crate::sodium::sodium_crypto::{encrypt_json, get_key};
+use crate::versioning::{expected_version, version_conflict, versioned_response, ExpectedVersion};
+
+// Everything a CRUD action needs from the incoming request.
+pub struct CrudContext {
+    pub state: Arc<AppState>,
+    pub claims: Claims,
+    pub audit: AuditContext,
+    pub headers: HeaderMap,
+}
+
+impl FromRequestParts<Arc<AppState>> for CrudContext {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let claims = Claims::from_request_parts(parts, state).await?;
+        let request_id = RequestId::from_request_parts(parts, state).await.unwrap_or_else(|never| match never {});
+        Ok(CrudContext {
+            state: state.clone(),
+            audit: AuditContext::new(&claims, &request_id),
+            claims,
+            headers: parts.headers.clone(),
+        })
+    }
+}
+
+// The operations every resource supports, whatever shape the client request had.
+pub enum CrudAction<R: Resource> {
+    Create(R),
+    ReadAll { include_deleted: bool },
+    Read { key: R::Key, include_deleted: bool },
+    Update { key: R::Key, version: Option<i32>, row: R },
+    Delete(R::Key),
+    Restore(R::Key),
+    Purge(R::Key),
+}
+
+// Runs an action for the JSON API: rows come back sealed, single rows with their ETag.
+pub async fn execute<R: Resource>(ctx: &CrudContext, action: CrudAction<R>) -> Result<Response, ApiError> {
+    match action {
+        CrudAction::Create(row) => Ok(encrypted(&create(ctx, row).await?)?.into_response()),
+        CrudAction::ReadAll { include_deleted } => Ok(encrypted(&read_all::<R>(ctx, include_deleted).await?)?.into_response()),
+        CrudAction::Read { key, include_deleted } => {
+            let row = read::<R>(ctx, key, include_deleted).await?;
+            versioned_response(&row, row.row_version())
+        }
+        CrudAction::Update { key, version, row } => {
+            let row = update(ctx, key, version, row).await?;
+            versioned_response(&row, row.row_version())
+        }
+        CrudAction::Delete(key) => Ok(Json(delete::<R>(ctx, key).await?).into_response()),
+        CrudAction::Restore(key) => Ok(Json(restore::<R>(ctx, key).await?).into_response()),
+        CrudAction::Purge(key) => Ok(Json(purge::<R>(ctx, key).await?).into_response()),
+    }
+}
+
+#[instrument(name = "db.create", skip_all, fields(table = R::TABLE))]
+pub async fn create<R: Resource>(ctx: &CrudContext, row: R) -> Result<R, ApiError> {
+    row.validate()?;
+    let mut tx = ctx.state.db.begin().await?;
+    let row = create_in(&mut tx, &ctx.audit, row).await?;
+    tx.commit().await?;
+    Ok(row)
+}
+
+// The write half of each action runs inside a transaction the caller owns and commits, so
+// a batch can chain several actions in one transaction. Rows are validated by the caller
+// first, so a rejected payload never opens a transaction.
+pub async fn create_in<R: Resource>(tx: &mut Transaction<'_, MySql>, audit: &AuditContext, mut row: R) -> Result<R, ApiError> {
+    let query_str = insert_sql::<R>();
+
+    let mut insert = query(&query_str);
+    for column in R::COLUMNS {
+        insert = row.bind_column(column, insert);
+    }
+
+    match insert.execute(&mut **tx).await {
+        Ok(done) => {
+            if row.generates_key() {
+                row.set_generated_key(done.last_insert_id())?;
+            }
+            audit.record(tx, "create", R::TABLE, json!(row.key()), None, Some(&row)).await?;
+            Ok(row)
+        }
+        Err(e) => {
+            error!(error = ?e, "Error creating {}", R::TABLE);
+            Err(e.into())
+        }
+    }
+}
+
+#[instrument(name = "db.read_all", skip_all, fields(table = R::TABLE))]
+pub async fn read_all<R: Resource>(ctx: &CrudContext, include_deleted: bool) -> Result<Vec<R>, ApiError> {
+    if include_deleted {
+        ctx.claims.require_admin()?;
+    }
+    let query_str = select_all_sql::<R>();
+
+    let rows = query(&query_str)
+        .bind(include_deleted)
+        .try_map(|row: MySqlRow| R::from_row(&row))
+        .fetch_all(&ctx.state.db)
+        .await;
+
+    match rows {
+        Ok(data) => Ok(data),
+        Err(e) => {
+            error!(error = ?e, "Error fetching {}", R::TABLE);
+            Err(e.into())
+        }
+    }
+}
+
+// Rows buffered ahead of a slow stream consumer before the database cursor waits.
+const STREAM_BUFFER: usize = 64;
+
+// Every row in key order, passed on one at a time as MySQL returns them, for callers that
+// stream large results instead of reading them into memory.
+pub fn stream_all<R: Resource>(ctx: &CrudContext, include_deleted: bool) -> Result<mpsc::Receiver<Result<R, ApiError>>, ApiError> {
+    stream_rows(ctx, RowFilter::new(include_deleted))
+}
+
+// The rows matching a filter, in key order. The query runs on its own task and stops once
+// the receiver is dropped.
+pub fn stream_rows<R: Resource>(ctx: &CrudContext, filter: RowFilter) -> Result<mpsc::Receiver<Result<R, ApiError>>, ApiError> {
+    if filter.include_deleted {
+        ctx.claims.require_admin()?;
+    }
+
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+    let pool = ctx.state.db.clone();
+    let span = info_span!("db.stream_rows", table = R::TABLE);
+    tokio::spawn(async move {
+        let query_str = filter.select_sql::<R>();
+        let mut rows = filter.bind(query(&query_str))
+            .try_map(|row: MySqlRow| R::from_row(&row))
+            .fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let failed = row.is_err();
+            let row = row.map_err(|e| {
+                error!(error = ?e, "Error streaming {}", R::TABLE);
+                ApiError::from(e)
+            });
+            if sender.send(row).await.is_err() || failed {
+                break;
+            }
+        }
+    }.instrument(span));
+    Ok(receiver)
+}
+
+#[instrument(name = "db.read", skip_all, fields(table = R::TABLE))]
+pub async fn read<R: Resource>(ctx: &CrudContext, key: R::Key, include_deleted: bool) -> Result<R, ApiError> {
+    if include_deleted {
+        ctx.claims.require_admin()?;
+    }
+    let query_str = select_sql::<R>();
+
+    let row = bind_key::<R>(&key, query(&query_str))
+        .bind(include_deleted)
+        .try_map(|row: MySqlRow| R::from_row(&row))
+        .fetch_optional(&ctx.state.db)
+        .await?;
+
+    row.ok_or_else(not_found::<R>)
+}
+
+// A read that sees the transaction's own uncommitted writes.
+pub async fn read_in<R: Resource>(tx: &mut Transaction<'_, MySql>, claims: &Claims, key: R::Key, include_deleted: bool) -> Result<R, ApiError> {
+    if include_deleted {
+        claims.require_admin()?;
+    }
+    let query_str = select_sql::<R>();
+
+    let row = bind_key::<R>(&key, query(&query_str))
+        .bind(include_deleted)
+        .try_map(|row: MySqlRow| R::from_row(&row))
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    row.ok_or_else(not_found::<R>)
+}
+
+#[instrument(name = "db.update", skip_all, fields(table = R::TABLE))]
+// `version` is the body's version field; an If-Match header on the request takes precedence.
+pub async fn update<R: Resource>(ctx: &CrudContext, key: R::Key, version: Option<i32>, mut row: R) -> Result<R, ApiError> {
+    let expected = expected_version(&ctx.headers, version)?;
+    // The key comes from the request, not from the row body
+    row.set_key(key.clone());
+    row.validate()?;
+    let mut tx = ctx.state.db.begin().await?;
+    let row = update_in(&mut tx, &ctx.audit, key, expected, row).await?;
+    tx.commit().await?;
+    Ok(row)
+}
+
+pub async fn update_in<R: Resource>(
+    tx: &mut Transaction<'_, MySql>,
+    audit: &AuditContext,
+    key: R::Key,
+    expected: ExpectedVersion,
+    row: R,
+) -> Result<R, ApiError> {
+    let query_str = update_sql::<R>();
+
+    let before = fetch_for_update::<R>(tx, &key).await?;
+
+    let mut update = query(&query_str);
+    for column in data_columns::<R>() {
+        update = row.bind_column(column, update);
+    }
+    let result = bind_key::<R>(&key, update)
+        .bind(expected.value())
+        .execute(&mut **tx)
+        .await;
+
+    match result {
+        Ok(done) if done.rows_affected() == 0 => match before {
+            Some(current) if current.deleted_at().is_none() => Err(version_conflict(expected, &current, current.row_version())),
+            _ => Err(not_found::<R>()),
+        },
+        Ok(_) => {
+            let after = fetch_for_update::<R>(tx, &key).await?;
+            audit.record(tx, "update", R::TABLE, json!(key), before.as_ref(), after.as_ref()).await?;
+
+            after.ok_or_else(|| ApiError::internal("row missing after write in the same transaction"))
+        }
+        Err(e) => {
+            error!(error = ?e, "Error updating {}", R::TABLE);
+            Err(e.into())
+        }
+    }
+}
+
+// Soft delete: the row (and its CASCADE_TABLES rows) stay in place stamped with one shared
+// deleted_at, mirroring what ON DELETE CASCADE would remove, so restore can undo exactly that.
+#[instrument(name = "db.delete", skip_all, fields(table = R::TABLE))]
+pub async fn delete<R: Resource>(ctx: &CrudContext, key: R::Key) -> Result<String, ApiError> {
+    let mut tx = ctx.state.db.begin().await?;
+    let message = delete_in::<R>(&mut tx, &ctx.audit, key).await?;
+    tx.commit().await?;
+    Ok(message)
+}
+
+pub async fn delete_in<R: Resource>(tx: &mut Transaction<'_, MySql>, audit: &AuditContext, key: R::Key) -> Result<String, ApiError> {
+    let deleted_at = query_scalar::<_, NaiveDateTime>("SELECT NOW()")
+        .fetch_one(&mut **tx)
+        .await?;
+
+    let before = fetch_for_update::<R>(tx, &key).await?;
+    if before.as_ref().is_none_or(|row| row.deleted_at().is_some()) {
+        return Err(not_found::<R>());
+    }
+
+    for table in std::iter::once(R::TABLE).chain(R::CASCADE_TABLES.iter().copied()) {
+        let query_str = soft_delete_sql::<R>(table);
+        bind_key::<R>(&key, query(&query_str).bind(deleted_at))
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                error!(error = ?e, "Error deleting {}", table);
+                ApiError::from(e)
+            })?;
+    }
+
+    let after = fetch_for_update::<R>(tx, &key).await?;
+    audit.record(tx, "delete", R::TABLE, json!(key), before.as_ref(), after.as_ref()).await?;
+
+    Ok(format!("{} deleted successfully", R::NAME))
+}
+
+// Restores the row and the cascaded rows deleted along with it. Rows that were deleted on
+// their own beforehand have a different deleted_at and stay deleted.
+#[instrument(name = "db.restore", skip_all, fields(table = R::TABLE))]
+pub async fn restore<R: Resource>(ctx: &CrudContext, key: R::Key) -> Result<String, ApiError> {
+    let mut tx = ctx.state.db.begin().await?;
+    let message = restore_in::<R>(&mut tx, &ctx.audit, key).await?;
+    tx.commit().await?;
+    Ok(message)
+}
+
+pub async fn restore_in<R: Resource>(tx: &mut Transaction<'_, MySql>, audit: &AuditContext, key: R::Key) -> Result<String, ApiError> {
+    let before = fetch_for_update::<R>(tx, &key).await?;
+    let deleted_at = before.as_ref().and_then(R::deleted_at).ok_or_else(not_found::<R>)?;
+
+    for table in std::iter::once(R::TABLE).chain(R::CASCADE_TABLES.iter().copied()) {
+        let query_str = restore_sql::<R>(table);
+        bind_key::<R>(&key, query(&query_str))
+            .bind(deleted_at)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                error!(error = ?e, "Error restoring {}", table);
+                ApiError::from(e)
+            })?;
+    }
+
+    let after = fetch_for_update::<R>(tx, &key).await?;
+    audit.record(tx, "restore", R::TABLE, json!(key), before.as_ref(), after.as_ref()).await?;
+
+    Ok(format!("{} restored successfully", R::NAME))
+}
+
+// Hard delete, admin only. This is the only path that removes rows.
+#[instrument(name = "db.purge", skip_all, fields(table = R::TABLE))]
+pub async fn purge<R: Resource>(ctx: &CrudContext, key: R::Key) -> Result<String, ApiError> {
+    ctx.claims.require_admin()?;
+    let mut tx = ctx.state.db.begin().await?;
+    let message = purge_in::<R>(&mut tx, &ctx.claims, &ctx.audit, key).await?;
+    tx.commit().await?;
+    Ok(message)
+}
+
+pub async fn purge_in<R: Resource>(tx: &mut Transaction<'_, MySql>, claims: &Claims, audit: &AuditContext, key: R::Key) -> Result<String, ApiError> {
+    claims.require_admin()?;
+    let query_str = purge_sql::<R>();
+
+    let before = fetch_for_update::<R>(tx, &key).await?;
+
+    let result = bind_key::<R>(&key, query(&query_str))
+        .execute(&mut **tx)
+        .await;
+
+    match result {
+        Ok(done) if done.rows_affected() == 0 => Err(not_found::<R>()),
+        Ok(_) => {
+            audit.record(tx, "purge", R::TABLE, json!(key), before.as_ref(), None).await?;
+            Ok(format!("{} purged successfully", R::NAME))
+        }
+        Err(e) => {
+            error!(error = ?e, "Error purging {}", R::TABLE);
+            Err(e.into())
+        }
+    }
+}
+
+// Current state of a row, whether or not it is soft deleted, locked for the rest of the
+// transaction. Used for audit snapshots and version conflict responses.
+#[instrument(name = "db.fetch_for_update", skip_all, fields(table = R::TABLE))]
+pub async fn fetch_for_update<R: Resource>(tx: &mut Transaction<'_, MySql>, key: &R::Key) -> Result<Option<R>, ApiError> {
+    let query_str = select_for_update_sql::<R>();
+    bind_key::<R>(key, query(&query_str))
+        .try_map(|row: MySqlRow| R::from_row(&row))
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(ApiError::from)
+}
+
+fn not_found<R: Resource>() -> ApiError {
+    ApiError::not_found(format!("{} not found", R::NAME))
+}
+
+// The sealed JSON body every successful API response carries.
+pub fn encrypted<T: Serialize>(data: &T) -> Result<Json<String>, ApiError> {
+    let json_data = to_value(data).map_err(ApiError::internal)?;
+    let key = get_key();
+    let encrypted_data = encrypt_json(&json_data, &key).map_err(ApiError::internal)?;
+    Ok(Json(encrypted_data))
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+
+use crate::repository::resource::{MySqlQuery, Resource};
+
+// A value bound to one of a filter's placeholders.
+#[derive(Debug, Clone)]
+pub enum FilterValue {
+    Int(i32),
+    Text(String),
+    Date(NaiveDate),
+}
+
+// Extra WHERE conditions for the streaming queries. Each condition is an SQL fragment whose
+// placeholders are bound, in order, from the values added with it.
+#[derive(Debug, Clone, Default)]
+pub struct RowFilter {
+    pub include_deleted: bool,
+    conditions: Vec<String>,
+    values: Vec<FilterValue>,
+}
+
+impl RowFilter {
+    pub fn new(include_deleted: bool) -> Self {
+        RowFilter { include_deleted, ..Default::default() }
+    }
+
+    pub fn condition(&mut self, sql: impl Into<String>, values: impl IntoIterator<Item = FilterValue>) {
+        self.conditions.push(sql.into());
+        self.values.extend(values);
+    }
+
+    // Unbounded and in key order, for streaming whole tables.
+    pub fn select_sql<R: Resource>(&self) -> String {
+        let conditions = self.conditions.iter().map(|condition| format!(" AND ({})", condition)).collect::<String>();
+        format!(
+            "SELECT * FROM {} WHERE (deleted_at IS NULL OR ?){} ORDER BY {}",
+            R::TABLE, conditions, R::KEY_COLUMNS.join(", ")
+        )
+    }
+
+    pub fn bind<'q>(&'q self, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        self.values.iter().fold(query.bind(self.include_deleted), |query, value| match value {
+            FilterValue::Int(value) => query.bind(*value),
+            FilterValue::Text(value) => query.bind(value.as_str()),
+            FilterValue::Date(value) => query.bind(*value),
+        })
+    }
+}
@@ -0,0 +1,10 @@
+pub mod resource;
+pub mod crud;
+pub mod filter;
+pub mod bulk;
+pub mod rest;
+
+pub use resource::{BindColumns, MySqlQuery, Resource};
+pub use crud::{execute, stream_all, stream_rows, CrudAction, CrudContext};
+pub use filter::{FilterValue, RowFilter};
+pub use rest::rest_routes;
@@ -0,0 +1,119 @@
+use chrono::NaiveDateTime;
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::{mysql::{MySqlArguments, MySqlRow}, query::Query, FromRow, MySql};
+
+use crate::error::ApiError;
+use crate::validation::Validate;
+
+pub type MySqlQuery<'q> = Query<'q, MySql, MySqlArguments>;
+
+// Binds a value by column name, so the generic queries can be built from column lists.
+pub trait BindColumns {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q>;
+}
+
+// A table the generic CRUD actions can serve. Adding a table means a model struct, a key
+// struct, and an impl of this trait plus BindColumns for both.
+pub trait Resource:
+    Serialize + DeserializeOwned + for<'r> FromRow<'r, MySqlRow> + BindColumns + Validate + Send + Sync + Unpin + 'static
+{
+    // The fields a client uses to address one row; serialized as the audit log's resource_key.
+    type Key: BindColumns + Serialize + DeserializeOwned + Clone + Send + Sync + 'static;
+
+    const TABLE: &'static str;
+    // Used in messages such as "Department not found".
+    const NAME: &'static str;
+    const KEY_COLUMNS: &'static [&'static str];
+    // Every column a create inserts, key columns included. Updates write the non-key ones.
+    const COLUMNS: &'static [&'static str];
+    // Tables whose rows share this resource's key columns and follow it through soft delete and restore.
+    const CASCADE_TABLES: &'static [&'static str] = &[];
+
+    fn key(&self) -> Self::Key;
+    fn set_key(&mut self, key: Self::Key);
+    fn row_version(&self) -> i32;
+    fn deleted_at(&self) -> Option<NaiveDateTime>;
+
+    // For tables whose key MySQL can allocate (AUTO_INCREMENT): whether this row left it out,
+    // and the hook that fills it in from LAST_INSERT_ID() after the insert.
+    fn generates_key(&self) -> bool {
+        false
+    }
+    fn set_generated_key(&mut self, _id: u64) -> Result<(), ApiError> {
+        Ok(())
+    }
+}
+
+pub fn bind_key<'q, R: Resource>(key: &'q R::Key, mut query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+    for column in R::KEY_COLUMNS {
+        query = key.bind_column(column, query);
+    }
+    query
+}
+
+pub fn key_filter<R: Resource>() -> String {
+    R::KEY_COLUMNS.iter().map(|column| format!("{} = ?", column)).collect::<Vec<_>>().join(" AND ")
+}
+
+pub fn data_columns<R: Resource>() -> impl Iterator<Item = &'static str> {
+    R::COLUMNS.iter().copied().filter(|column| !R::KEY_COLUMNS.contains(column))
+}
+
+pub fn select_sql<R: Resource>() -> String {
+    format!("SELECT * FROM {} WHERE {} AND (deleted_at IS NULL OR ?)", R::TABLE, key_filter::<R>())
+}
+
+pub fn select_all_sql<R: Resource>() -> String {
+    format!("SELECT * FROM {} WHERE (deleted_at IS NULL OR ?) LIMIT 10", R::TABLE)
+}
+
+pub fn select_for_update_sql<R: Resource>() -> String {
+    format!("SELECT * FROM {} WHERE {} FOR UPDATE", R::TABLE, key_filter::<R>())
+}
+
+pub fn insert_sql<R: Resource>() -> String {
+    let placeholders = vec!["?"; R::COLUMNS.len()].join(", ");
+    format!("INSERT INTO {} ({}) VALUES ({})", R::TABLE, R::COLUMNS.join(", "), placeholders)
+}
+
+// One INSERT for several rows, for bulk imports.
+pub fn insert_many_sql<R: Resource>(rows: usize) -> String {
+    let placeholders = format!("({})", vec!["?"; R::COLUMNS.len()].join(", "));
+    format!("INSERT INTO {} ({}) VALUES {}", R::TABLE, R::COLUMNS.join(", "), vec![placeholders; rows].join(", "))
+}
+
+pub fn update_sql<R: Resource>() -> String {
+    let assignments = data_columns::<R>().map(|column| format!("{} = ?, ", column)).collect::<String>();
+    format!(
+        "UPDATE {} SET {}row_version = row_version + 1 WHERE {} AND deleted_at IS NULL AND row_version = ?",
+        R::TABLE, assignments, key_filter::<R>()
+    )
+}
+
+pub fn soft_delete_sql<R: Resource>(table: &str) -> String {
+    format!("UPDATE {} SET deleted_at = ?, row_version = row_version + 1 WHERE {} AND deleted_at IS NULL", table, key_filter::<R>())
+}
+
+pub fn restore_sql<R: Resource>(table: &str) -> String {
+    format!("UPDATE {} SET deleted_at = NULL, row_version = row_version + 1 WHERE {} AND deleted_at = ?", table, key_filter::<R>())
+}
+
+pub fn purge_sql<R: Resource>() -> String {
+    format!("DELETE FROM {} WHERE {}", R::TABLE, key_filter::<R>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dept_emp::models::DeptEmp;
+
+    #[test]
+    fn rows_are_addressed_by_their_full_key() {
+        assert_eq!(select_for_update_sql::<DeptEmp>(), "SELECT * FROM dept_emp WHERE emp_no = ? AND dept_no = ? AND from_date = ? FOR UPDATE");
+        assert_eq!(
+            update_sql::<DeptEmp>(),
+            "UPDATE dept_emp SET to_date = ?, row_version = row_version + 1 WHERE emp_no = ? AND dept_no = ? AND from_date = ? AND deleted_at IS NULL AND row_version = ?"
+        );
+        assert_eq!(purge_sql::<DeptEmp>(), "DELETE FROM dept_emp WHERE emp_no = ? AND dept_no = ? AND from_date = ?");
+    }
+}
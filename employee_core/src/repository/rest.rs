@@ -0,0 +1,107 @@
+use axum::{
+    extract::{rejection::{JsonRejection, PathRejection, QueryRejection}, Json, Path, Query},
+    http::StatusCode,
+    response::Response,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::db::AppState;
+use crate::error::ApiError;
+use crate::repository::crud::{execute, CrudAction, CrudContext};
+use crate::repository::resource::Resource;
+use crate::validation::parse_request;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReadParams {
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteParams {
+    #[serde(default)]
+    pub purge: bool,
+}
+
+// Resource-oriented routes over the same CRUD actions as the POST action endpoint:
+//   GET    /                      read_all (?include_deleted=true for admins)
+//   GET    /{key...}              read, with an ETag
+//   PUT    /{key...}              update, If-Match required
+//   DELETE /{key...}              soft delete (?purge=true for an admin hard delete)
+//   POST   /{key...}/restore      restore
+// The key segments are the resource's KEY_COLUMNS in order, e.g. /salaries/{emp_no}/{from_date}.
+// POST / stays with the module's action handler, which treats a body without "action" as a create.
+pub fn rest_routes<R: Resource>() -> Router<Arc<AppState>> {
+    let key_path = key_path::<R>();
+    Router::new()
+        .route("/", get(list::<R>))
+        .route(&key_path, get(read_one::<R>).put(replace::<R>).delete(remove::<R>))
+        .route(&format!("{}/restore", key_path), post(restore::<R>))
+}
+
+pub fn key_path<R: Resource>() -> String {
+    R::KEY_COLUMNS.iter().map(|column| format!("/{{{}}}", column)).collect()
+}
+
+// POST / serves both APIs: a body with an "action" field is an action request,
+// anything else is the row itself for a REST create.
+pub fn is_action_request(payload: &Value) -> bool {
+    payload.get("action").is_some()
+}
+
+pub async fn create<R: Resource>(ctx: &CrudContext, payload: Value) -> Result<Response, ApiError> {
+    let row: R = parse_request(payload)?;
+    let mut response = execute(ctx, CrudAction::Create(row)).await?;
+    *response.status_mut() = StatusCode::CREATED;
+    Ok(response)
+}
+
+async fn list<R: Resource>(
+    ctx: CrudContext,
+    params: Result<Query<ReadParams>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Query(params) = params?;
+    execute(&ctx, CrudAction::<R>::ReadAll { include_deleted: params.include_deleted }).await
+}
+
+async fn read_one<R: Resource>(
+    ctx: CrudContext,
+    key: Result<Path<R::Key>, PathRejection>,
+    params: Result<Query<ReadParams>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let (Path(key), Query(params)) = (key?, params?);
+    execute(&ctx, CrudAction::<R>::Read { key, include_deleted: params.include_deleted }).await
+}
+
+async fn replace<R: Resource>(
+    ctx: CrudContext,
+    key: Result<Path<R::Key>, PathRejection>,
+    payload: Result<Json<Value>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let (Path(key), Json(payload)) = (key?, payload?);
+    let row: R = parse_request(payload)?;
+    // Only If-Match carries the version here; the body is just the row.
+    execute(&ctx, CrudAction::Update { key, version: None, row }).await
+}
+
+async fn remove<R: Resource>(
+    ctx: CrudContext,
+    key: Result<Path<R::Key>, PathRejection>,
+    params: Result<Query<DeleteParams>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let (Path(key), Query(params)) = (key?, params?);
+    let action = if params.purge { CrudAction::<R>::Purge(key) } else { CrudAction::<R>::Delete(key) };
+    execute(&ctx, action).await
+}
+
+async fn restore<R: Resource>(
+    ctx: CrudContext,
+    key: Result<Path<R::Key>, PathRejection>,
+) -> Result<Response, ApiError> {
+    let Path(key) = key?;
+    execute(&ctx, CrudAction::<R>::Restore(key)).await
+}
@@ -0,0 +1,67 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::convert::Infallible;
+use std::time::Instant;
+use tracing::{info, info_span, Instrument};
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+// The id of the request being handled, for places that only see a Response (error bodies).
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+// Identifies one API call across the response headers and the audit log.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+// Reuses the caller's X-Request-Id when it looks sane, otherwise generates one,
+// and echoes it back on the response.
+pub async fn request_id_middleware(
+    mut request: axum::http::Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    let request_id = request.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= 64)
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    request.extensions_mut().insert(RequestId(request_id.clone()));
+
+    // Everything logged while handling the request carries its id. The path is logged
+    // without the query string, which can carry encrypted payloads.
+    let span = info_span!("request", request_id = %request_id, method = %request.method(), path = %request.uri().path());
+    let started = Instant::now();
+
+    let mut response = CURRENT_REQUEST_ID.scope(request_id.clone(), next.run(request)).instrument(span.clone()).await;
+    span.in_scope(|| info!(status = response.status().as_u16(), elapsed_ms = started.elapsed().as_millis() as u64, "request finished"));
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+impl<S> FromRequestParts<S> for RequestId
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions
+            .get::<RequestId>()
+            .cloned()
+            .unwrap_or_else(|| RequestId(Uuid::new_v4().to_string())))
+    }
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use sqlx::FromRow;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::db::initial_row_version;
+use crate::error::ApiError;
+use crate::repository::{BindColumns, CrudAction, MySqlQuery, Resource};
+use crate::validation::{Validate, ValidationErrors};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Salary {
+    pub emp_no: i32,
+    pub salary: i32,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    #[serde(default = "initial_row_version")]
+    pub row_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// Either a new absolute salary or a percentage change, effective from `effective_date`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SalaryAdjustment {
+    pub amount: Option<i32>,
+    pub percent: Option<f64>,
+    pub effective_date: NaiveDate,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SalaryAdjustmentResult {
+    pub emp_no: i32,
+    pub before: i32,
+    pub after: i32,
+    pub effective_date: NaiveDate,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DepartmentAdjustmentResult {
+    pub dept_no: String,
+    pub effective_date: NaiveDate,
+    pub dry_run: bool,
+    pub total_before: i64,
+    pub total_after: i64,
+    pub adjustments: Vec<SalaryAdjustmentResult>,
+}
+
+impl Validate for Salary {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.positive("emp_no", self.emp_no as i64);
+        errors.positive("salary", self.salary as i64);
+        errors.not_before("to_date", self.to_date, "from_date", self.from_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for SalaryAdjustment {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        match (self.amount, self.percent) {
+            (Some(amount), None) => errors.positive("amount", amount as i64),
+            (None, Some(percent)) => {
+                if !percent.is_finite() || percent <= -100.0 {
+                    errors.add("percent", "must be a number greater than -100");
+                }
+            }
+            _ => errors.add("amount", "exactly one of amount or percent is required"),
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct SalaryKey {
+    pub emp_no: i32,
+    pub from_date: NaiveDate,
+}
+
+impl BindColumns for SalaryKey {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "from_date" => query.bind(self.from_date),
+            _ => unreachable!("SalaryKey has no column {}", column),
+        }
+    }
+}
+
+impl BindColumns for Salary {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "salary" => query.bind(self.salary),
+            "from_date" => query.bind(self.from_date),
+            "to_date" => query.bind(self.to_date),
+            _ => unreachable!("salaries has no column {}", column),
+        }
+    }
+}
+
+impl Resource for Salary {
+    type Key = SalaryKey;
+
+    const TABLE: &'static str = "salaries";
+    const NAME: &'static str = "Salary";
+    const KEY_COLUMNS: &'static [&'static str] = &["emp_no", "from_date"];
+    const COLUMNS: &'static [&'static str] = &["emp_no", "salary", "from_date", "to_date"];
+    fn key(&self) -> SalaryKey {
+        SalaryKey { emp_no: self.emp_no, from_date: self.from_date }
+    }
+
+    fn set_key(&mut self, key: SalaryKey) {
+        self.emp_no = key.emp_no;
+        self.from_date = key.from_date;
+    }
+
+    fn row_version(&self) -> i32 {
+        self.row_version
+    }
+
+    fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.deleted_at
+    }
+}
+
+// Everything POST /salaries accepts, selected by the "action" field.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum SalaryRequest {
+    Create { salary: Salary },
+    ReadAll {
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Read {
+        emp_no: i32,
+        from_date: NaiveDate,
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Update {
+        emp_no: i32,
+        from_date: NaiveDate,
+        version: Option<i32>,
+        salary: Salary,
+    },
+    Delete { emp_no: i32, from_date: NaiveDate },
+    Restore { emp_no: i32, from_date: NaiveDate },
+    Purge { emp_no: i32, from_date: NaiveDate },
+    Adjust { emp_no: i32, adjustment: SalaryAdjustment },
+    AdjustDepartment {
+        dept_no: String,
+        adjustment: SalaryAdjustment,
+        #[serde(default)]
+        dry_run: bool,
+    },
+}
+
+impl SalaryRequest {
+    // The generic CRUD action this request maps to; adjust and adjust_department have their own handlers.
+    pub fn into_action(self) -> Result<CrudAction<Salary>, ApiError> {
+        let action = match self {
+            SalaryRequest::Create { salary } => CrudAction::Create(salary),
+            SalaryRequest::ReadAll { include_deleted } => CrudAction::ReadAll { include_deleted },
+            SalaryRequest::Read { emp_no, from_date, include_deleted } => CrudAction::Read { key: SalaryKey { emp_no, from_date }, include_deleted },
+            SalaryRequest::Update { emp_no, from_date, version, salary } => CrudAction::Update { key: SalaryKey { emp_no, from_date }, version, row: salary },
+            SalaryRequest::Delete { emp_no, from_date } => CrudAction::Delete(SalaryKey { emp_no, from_date }),
+            SalaryRequest::Restore { emp_no, from_date } => CrudAction::Restore(SalaryKey { emp_no, from_date }),
+            SalaryRequest::Purge { emp_no, from_date } => CrudAction::Purge(SalaryKey { emp_no, from_date }),
+            SalaryRequest::Adjust { .. } | SalaryRequest::AdjustDepartment { .. } => {
+                return Err(ApiError::Unprocessable("adjust and adjust_department are not CRUD actions".to_string()));
+            }
+        };
+        Ok(action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn fields(result: Result<(), ValidationErrors>) -> Vec<String> {
+        result.err().map(|errors| errors.errors.into_iter().map(|error| error.field).collect()).unwrap_or_default()
+    }
+
+    fn salary(amount: i32, from_date: &str, to_date: &str) -> Salary {
+        Salary { emp_no: 10001, salary: amount, from_date: date(from_date), to_date: date(to_date), row_version: initial_row_version(), deleted_at: None }
+    }
+
+    #[test]
+    fn salary_must_be_positive() {
+        assert!(fields(salary(60117, "1986-06-26", "1987-06-26").validate()).is_empty());
+        assert_eq!(fields(salary(0, "1986-06-26", "1987-06-26").validate()), ["salary"]);
+        assert_eq!(fields(salary(-1, "1986-06-26", "1987-06-26").validate()), ["salary"]);
+    }
+
+    #[test]
+    fn to_date_must_not_be_before_from_date() {
+        assert_eq!(fields(salary(60117, "1987-06-26", "1986-06-26").validate()), ["to_date"]);
+    }
+
+    fn adjustment(amount: Option<i32>, percent: Option<f64>) -> SalaryAdjustment {
+        SalaryAdjustment { amount, percent, effective_date: date("2000-01-01") }
+    }
+
+    #[test]
+    fn an_adjustment_takes_exactly_one_of_amount_or_percent() {
+        assert!(fields(adjustment(Some(1000), None).validate()).is_empty());
+        assert!(fields(adjustment(None, Some(-10.0)).validate()).is_empty());
+        assert_eq!(fields(adjustment(None, None).validate()), ["amount"]);
+        assert_eq!(fields(adjustment(Some(1000), Some(5.0)).validate()), ["amount"]);
+    }
+
+    #[test]
+    fn an_adjustment_cannot_wipe_out_the_salary() {
+        assert_eq!(fields(adjustment(Some(0), None).validate()), ["amount"]);
+        assert_eq!(fields(adjustment(None, Some(-100.0)).validate()), ["percent"]);
+        assert_eq!(fields(adjustment(None, Some(f64::NAN)).validate()), ["percent"]);
+    }
+}
@@ -0,0 +1,87 @@
+use sodiumoxide::crypto::secretbox;
+use serde_json::{json, Value, to_vec, from_slice};
+use sodiumoxide::base64::{encode, decode, Variant};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tracing::warn;
+
+use crate::metrics;
+use crate::telemetry::Redacted;
+
+static ENCRYPTION_KEY: OnceLock<secretbox::Key> = OnceLock::new();
+static KEY_SOURCE: OnceLock<KeySource> = OnceLock::new();
+
+// Where the key installed at startup came from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeySource {
+    Configured,
+    Generated,
+}
+
+pub fn encrypt_json(data: &Value, key: &secretbox::Key) -> Result<String, String> {
+    let started = Instant::now();
+    let nonce = secretbox::gen_nonce();
+    let plaintext = to_vec(data).map_err(|_| "Failed to serialize JSON".to_string())?;
+    let ciphertext = secretbox::seal(&plaintext, &nonce, key);
+
+    let mut combined = nonce.as_ref().to_vec();
+    combined.extend_from_slice(&ciphertext);
+
+    let encoded = encode(&combined, Variant::Original);
+    metrics::observe_encrypt(started);
+    Ok(encoded)
+}
+
+pub fn decrypt_json(encrypted_data: &str, key: &secretbox::Key) -> Result<Value, String> {
+    let combined = decode(encrypted_data, Variant::Original).map_err(|e| format!("Failed to decode base64: {:?}", e))?;
+    let nonce_len = secretbox::NONCEBYTES;
+    let nonce_bytes = &combined[..nonce_len];
+    let ciphertext = &combined[nonce_len..];
+
+    let nonce = secretbox::Nonce::from_slice(nonce_bytes).ok_or("Failed to create nonce from slice".to_string())?;
+
+    let plaintext = secretbox::open(ciphertext, &nonce, key).map_err(|_| "Failed to decrypt data".to_string())?;
+
+    from_slice(&plaintext).map_err(|_| "Failed to deserialize JSON".to_string())
+}
+
+pub fn get_key() -> secretbox::Key {
+    ENCRYPTION_KEY.get_or_init(secretbox::gen_key).clone()
+}
+
+pub fn decrypt_string(encrypted_data: &str) -> Result<Value, String> {
+    let key = get_key();
+    decrypt_json(encrypted_data, &key)
+}
+
+// What initialize_key installed, for the readiness check; None if it has not run or failed.
+// Not derived from ENCRYPTION_KEY, which get_key fills on first use.
+pub fn key_source() -> Option<KeySource> {
+    KEY_SOURCE.get().copied()
+}
+
+pub fn get_key_base64() -> String {
+    encode(get_key().as_ref(), Variant::Original)
+}
+
+pub fn parse_key(base64_key: &str) -> Result<secretbox::Key, String> {
+    let key_bytes = decode(base64_key, Variant::Original).map_err(|_| "not valid base64".to_string())?;
+    secretbox::Key::from_slice(&key_bytes)
+        .ok_or_else(|| format!("expected {} bytes, got {}", secretbox::KEYBYTES, key_bytes.len()))
+}
+
+// Installs the configured key at startup. Without one a new key is generated and printed,
+// so anything encrypted before a restart can no longer be decrypted.
+pub fn initialize_key(configured: Option<&str>) -> Result<(), String> {
+    let (key, source) = match configured {
+        Some(base64_key) => (parse_key(base64_key)?, KeySource::Configured),
+        None => {
+            let key = secretbox::gen_key();
+            let generated = json!({ "sodium_key": encode(key.as_ref(), Variant::Original) });
+            warn!(generated = %Redacted(&generated), "No crypto.sodium_key configured, generated a temporary key (GET /sodium_key returns it)");
+            (key, KeySource::Generated)
+        }
+    };
+    ENCRYPTION_KEY.set(key).map_err(|_| "encryption key is already initialized".to_string())?;
+    KEY_SOURCE.set(source).map_err(|_| "encryption key is already initialized".to_string())
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    fmt::{self, Display},
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+use crate::config::{LogConfig, LogFormat};
+
+static REDACT: AtomicBool = AtomicBool::new(true);
+
+const REDACTED: &str = "[redacted]";
+
+// Installs the global subscriber. Every event carries the fields of the spans it happened in,
+// so lines logged while handling a request include its request_id.
+pub fn init(config: &LogConfig) {
+    REDACT.store(config.redact, Ordering::Relaxed);
+
+    let filter = EnvFilter::try_new(&config.filter).expect("log.filter was validated with the config");
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::NONE)
+        .with_target(true);
+
+    match config.format {
+        LogFormat::Json => builder.json().with_current_span(false).with_span_list(true).init(),
+        LogFormat::Text => builder.init(),
+    }
+}
+
+// Salaries, birth dates, keys and credentials never reach the logs unless redaction is
+// switched off (log.redact = false) for local debugging.
+pub fn is_sensitive(field: &str) -> bool {
+    let field = field.to_ascii_lowercase();
+    field.contains("salary")
+        || field == "birth_date"
+        || field == "key"
+        || field.ends_with("_key")
+        || field.contains("token")
+        || field.contains("secret")
+        || field.contains("password")
+        || field == "authorization"
+}
+
+pub fn redact(value: &Value) -> Value {
+    if !REDACT.load(Ordering::Relaxed) {
+        return value.clone();
+    }
+    redact_value(value)
+}
+
+fn redact_value(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields.iter()
+                .map(|(name, value)| {
+                    let value = if is_sensitive(name) { Value::String(REDACTED.to_string()) } else { redact_value(value) };
+                    (name.clone(), value)
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_value).collect()),
+        other => other.clone(),
+    }
+}
+
+// Logs any serializable value as redacted JSON: debug!(payload = %Redacted(&row), ...).
+pub struct Redacted<'a, T: Serialize>(pub &'a T);
+
+impl<T: Serialize> Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_value(self.0) {
+            Ok(value) => write!(f, "{}", redact(&value)),
+            Err(_) => write!(f, "{}", REDACTED),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sensitive_fields_match_in_any_case() {
+        for field in ["salary", "Salary", "new_salary", "birth_date", "BIRTH_DATE", "key", "sodium_key", "token", "access_token", "jwt_secret", "Password", "Authorization"] {
+            assert!(is_sensitive(field), "{} should be sensitive", field);
+        }
+    }
+
+    #[test]
+    fn other_fields_are_not_sensitive() {
+        for field in ["emp_no", "first_name", "hire_date", "dept_no", "keyword", "title", "authorized"] {
+            assert!(!is_sensitive(field), "{} should not be sensitive", field);
+        }
+    }
+
+    #[test]
+    fn nested_objects_and_arrays_are_redacted() {
+        let value = json!({
+            "emp_no": 10001,
+            "employee": { "first_name": "Georgi", "Birth_Date": "1953-09-02" },
+            "salaries": [{ "salary": 60117 }],
+            "changes": [{ "emp_no": 10001, "new_salary": 66074, "Token": "abc" }],
+            "headers": { "AUTHORIZATION": "Bearer abc" },
+        });
+        assert_eq!(
+            redact_value(&value),
+            json!({
+                "emp_no": 10001,
+                "employee": { "first_name": "Georgi", "Birth_Date": REDACTED },
+                "salaries": [{ "salary": REDACTED }],
+                "changes": [{ "emp_no": 10001, "new_salary": REDACTED, "Token": REDACTED }],
+                "headers": { "AUTHORIZATION": REDACTED },
+            })
+        );
+    }
+
+    #[test]
+    fn values_without_sensitive_fields_pass_through() {
+        let value = json!({ "emp_no": 10001, "titles": ["Engineer", "Senior Engineer"], "to_date": null, "current": true });
+        assert_eq!(redact_value(&value), value);
+        assert_eq!(redact_value(&json!("a salary of 60117")), json!("a salary of 60117"));
+    }
+
+    #[test]
+    fn redacted_displays_the_masked_json() {
+        let row = json!({ "emp_no": 10001, "salary": 60117 });
+        assert_eq!(Redacted(&row).to_string(), r#"{"emp_no":10001,"salary":"[redacted]"}"#);
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use sqlx::FromRow;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::db::initial_row_version;
+use crate::repository::{BindColumns, CrudAction, MySqlQuery, Resource};
+use crate::validation::{Validate, ValidationErrors};
+
+#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Title {
+    pub emp_no: i32,
+    pub title: String,
+    pub from_date: NaiveDate,
+    pub to_date: Option<NaiveDate>,
+    #[serde(default = "initial_row_version")]
+    pub row_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// Column limit from employees.sql: title VARCHAR(50).
+impl Validate for Title {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        errors.positive("emp_no", self.emp_no as i64);
+        errors.max_length("title", &self.title, 50);
+        if let Some(to_date) = self.to_date {
+            errors.not_before("to_date", to_date, "from_date", self.from_date);
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct TitleKey {
+    pub emp_no: i32,
+    pub title: String,
+    pub from_date: NaiveDate,
+}
+
+impl BindColumns for TitleKey {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "title" => query.bind(&self.title),
+            "from_date" => query.bind(self.from_date),
+            _ => unreachable!("TitleKey has no column {}", column),
+        }
+    }
+}
+
+impl BindColumns for Title {
+    fn bind_column<'q>(&'q self, column: &str, query: MySqlQuery<'q>) -> MySqlQuery<'q> {
+        match column {
+            "emp_no" => query.bind(self.emp_no),
+            "title" => query.bind(&self.title),
+            "from_date" => query.bind(self.from_date),
+            "to_date" => query.bind(self.to_date),
+            _ => unreachable!("titles has no column {}", column),
+        }
+    }
+}
+
+impl Resource for Title {
+    type Key = TitleKey;
+
+    const TABLE: &'static str = "titles";
+    const NAME: &'static str = "Title";
+    const KEY_COLUMNS: &'static [&'static str] = &["emp_no", "title", "from_date"];
+    const COLUMNS: &'static [&'static str] = &["emp_no", "title", "from_date", "to_date"];
+    fn key(&self) -> TitleKey {
+        TitleKey { emp_no: self.emp_no, title: self.title.clone(), from_date: self.from_date }
+    }
+
+    fn set_key(&mut self, key: TitleKey) {
+        self.emp_no = key.emp_no;
+        self.title = key.title;
+        self.from_date = key.from_date;
+    }
+
+    fn row_version(&self) -> i32 {
+        self.row_version
+    }
+
+    fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.deleted_at
+    }
+}
+
+// Everything POST /titles accepts, selected by the "action" field. The key of a title row
+// is (emp_no, title_name, from_date); "title" carries the row itself.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TitleRequest {
+    Create { title: Title },
+    ReadAll {
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Read {
+        emp_no: i32,
+        title_name: String,
+        from_date: NaiveDate,
+        #[serde(default)]
+        include_deleted: bool,
+    },
+    Update {
+        emp_no: i32,
+        title_name: String,
+        from_date: NaiveDate,
+        version: Option<i32>,
+        title: Title,
+    },
+    Delete { emp_no: i32, title_name: String, from_date: NaiveDate },
+    Restore { emp_no: i32, title_name: String, from_date: NaiveDate },
+    Purge { emp_no: i32, title_name: String, from_date: NaiveDate },
+}
+
+impl TitleRequest {
+    // The generic CRUD action this request maps to.
+    pub fn into_action(self) -> CrudAction<Title> {
+        match self {
+            TitleRequest::Create { title } => CrudAction::Create(title),
+            TitleRequest::ReadAll { include_deleted } => CrudAction::ReadAll { include_deleted },
+            TitleRequest::Read { emp_no, title_name, from_date, include_deleted } => CrudAction::Read { key: TitleKey { emp_no, title: title_name, from_date }, include_deleted },
+            TitleRequest::Update { emp_no, title_name, from_date, version, title } => CrudAction::Update { key: TitleKey { emp_no, title: title_name, from_date }, version, row: title },
+            TitleRequest::Delete { emp_no, title_name, from_date } => CrudAction::Delete(TitleKey { emp_no, title: title_name, from_date }),
+            TitleRequest::Restore { emp_no, title_name, from_date } => CrudAction::Restore(TitleKey { emp_no, title: title_name, from_date }),
+            TitleRequest::Purge { emp_no, title_name, from_date } => CrudAction::Purge(TitleKey { emp_no, title: title_name, from_date }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(to_date: Option<&str>) -> Title {
+        Title {
+            emp_no: 10001,
+            title: "Senior Engineer".to_string(),
+            from_date: "1986-06-26".parse().unwrap(),
+            to_date: to_date.map(|date| date.parse().unwrap()),
+            row_version: initial_row_version(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn an_open_title_has_no_end_to_check() {
+        assert!(title(None).validate().is_ok());
+        assert!(title(Some("1995-12-01")).validate().is_ok());
+    }
+
+    #[test]
+    fn a_closed_title_cannot_end_before_it_starts() {
+        let errors = title(Some("1985-01-01")).validate().unwrap_err();
+        assert_eq!(errors.errors[0].field, "to_date");
+    }
+
+    #[test]
+    fn the_title_must_fit_its_column() {
+        let errors = Title { title: "x".repeat(51), ..title(None) }.validate().unwrap_err();
+        assert_eq!(errors.errors[0].field, "title");
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::metrics;
+use crate::telemetry::redact;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+// All the problems found in one payload, reported together as a 422 (see ApiError::Validation).
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(FieldError { field: field.to_string(), message: message.into() });
+    }
+
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    // Column length in characters, as VARCHAR/CHAR count them.
+    pub fn max_length(&mut self, field: &str, value: &str, max: usize) {
+        if value.trim().is_empty() {
+            self.add(field, "must not be empty");
+        } else if value.chars().count() > max {
+            self.add(field, format!("must be at most {} characters", max));
+        }
+    }
+
+    pub fn positive(&mut self, field: &str, value: i64) {
+        if value <= 0 {
+            self.add(field, "must be greater than zero");
+        }
+    }
+
+    pub fn not_before(&mut self, field: &str, date: NaiveDate, other_field: &str, other: NaiveDate) {
+        if date < other {
+            self.add(field, format!("must not be before {}", other_field));
+        }
+    }
+}
+
+// Business rules and column limits for a payload that deserialized fine.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+// Deserializes a whole request payload, keeping serde's message and the path of the
+// offending field (e.g. "employee.birth_date") instead of collapsing everything into a bare 400.
+pub fn parse_request<T: DeserializeOwned>(value: Value) -> Result<T, ValidationErrors> {
+    tracing::debug!(payload = %redact(&value), "request payload");
+    let action = value.get("action").and_then(Value::as_str).map(str::to_string);
+    let request = serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        let field = if path == "." { "action".to_string() } else { path };
+        let mut errors = ValidationErrors::default();
+        errors.add(&field, e.into_inner().to_string());
+        errors
+    })?;
+    // Only a tag that parsed is a known action, so it is safe to use as a metrics label.
+    if let Some(action) = action {
+        metrics::record_action(&action);
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn messages(errors: ValidationErrors) -> Vec<(String, String)> {
+        errors.errors.into_iter().map(|error| (error.field, error.message)).collect()
+    }
+
+    #[test]
+    fn max_length_counts_characters_and_rejects_blank_values() {
+        let mut errors = ValidationErrors::default();
+        errors.max_length("a", "Zoë", 3);
+        errors.max_length("b", "   ", 3);
+        errors.max_length("c", "abcd", 3);
+        assert_eq!(messages(errors), [
+            ("b".to_string(), "must not be empty".to_string()),
+            ("c".to_string(), "must be at most 3 characters".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn positive_rejects_zero_and_negatives() {
+        let mut errors = ValidationErrors::default();
+        errors.positive("a", 1);
+        errors.positive("b", 0);
+        errors.positive("c", -1);
+        assert_eq!(messages(errors).into_iter().map(|(field, _)| field).collect::<Vec<_>>(), ["b", "c"]);
+    }
+
+    #[test]
+    fn not_before_allows_the_same_day() {
+        let mut errors = ValidationErrors::default();
+        errors.not_before("to_date", date("2000-01-01"), "from_date", date("2000-01-01"));
+        assert!(errors.into_result().is_ok());
+
+        let mut errors = ValidationErrors::default();
+        errors.not_before("to_date", date("1999-12-31"), "from_date", date("2000-01-01"));
+        assert_eq!(messages(errors), [("to_date".to_string(), "must not be before from_date".to_string())]);
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Payload {
+        inner: Inner,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Inner {
+        birth_date: NaiveDate,
+    }
+
+    #[test]
+    fn parse_request_reports_the_path_of_the_bad_field() {
+        let payload = parse_request::<Payload>(json!({ "inner": { "birth_date": "1953-09-02" } })).unwrap();
+        assert_eq!(payload.inner.birth_date, date("1953-09-02"));
+
+        let errors = parse_request::<Payload>(json!({ "inner": { "birth_date": "yesterday" } })).unwrap_err();
+        assert_eq!(errors.errors[0].field, "inner.birth_date");
+    }
+}
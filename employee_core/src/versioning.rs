@@ -0,0 +1,117 @@
+use axum::{
+    http::{header::{ETAG, IF_MATCH}, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::{json, to_value};
+
+use crate::error::ApiError;
+use crate::sodium::sodium_crypto::{encrypt_json, get_key};
+
+// The row_version a client last saw, taken from If-Match or the payload's "version" field.
+#[derive(Debug, Clone, Copy)]
+pub enum ExpectedVersion {
+    IfMatch(i32),
+    Body(i32),
+}
+
+impl ExpectedVersion {
+    pub fn value(&self) -> i32 {
+        match self {
+            ExpectedVersion::IfMatch(version) | ExpectedVersion::Body(version) => *version,
+        }
+    }
+}
+
+// Updates must say which version they were based on. A missing version is 428 so a client
+// that forgets it can never silently overwrite someone else's change.
+pub fn expected_version(headers: &HeaderMap, body_version: Option<i32>) -> Result<ExpectedVersion, ApiError> {
+    if let Some(if_match) = headers.get(IF_MATCH) {
+        let malformed = || ApiError::bad_request("If-Match must be a quoted row_version");
+        let tag = if_match.to_str().map_err(|_| malformed())?;
+        let version = tag.trim().trim_start_matches("W/").trim_matches('"').parse::<i32>().map_err(|_| malformed())?;
+        return Ok(ExpectedVersion::IfMatch(version));
+    }
+
+    body_version
+        .map(ExpectedVersion::Body)
+        .ok_or_else(|| ApiError::PreconditionRequired("Send the row_version as an If-Match header or a version field".to_string()))
+}
+
+pub fn etag(version: i32) -> Result<HeaderValue, ApiError> {
+    HeaderValue::from_str(&format!("\"{}\"", version)).map_err(ApiError::internal)
+}
+
+// Encrypted row plus its ETag, as returned by single-row reads.
+pub fn versioned_response<T: Serialize>(row: &T, version: i32) -> Result<Response, ApiError> {
+    let json_data = to_value(row).map_err(ApiError::internal)?;
+    let key = get_key();
+    let encrypted_data = encrypt_json(&json_data, &key).map_err(ApiError::internal)?;
+    Ok(([(ETAG, etag(version)?)], Json(encrypted_data)).into_response())
+}
+
+// 412 for a stale If-Match, 409 for a stale body version; either way the client gets
+// the current row so it can merge and retry.
+pub fn version_conflict<T: Serialize>(expected: ExpectedVersion, current: &T, current_version: i32) -> ApiError {
+    let status = match expected {
+        ExpectedVersion::IfMatch(_) => StatusCode::PRECONDITION_FAILED,
+        ExpectedVersion::Body(_) => StatusCode::CONFLICT,
+    };
+
+    let json_data = match to_value(current) {
+        Ok(json_data) => json_data,
+        Err(e) => return ApiError::internal(e),
+    };
+    let key = get_key();
+    let encrypted_data = match encrypt_json(&json_data, &key) {
+        Ok(encrypted_data) => encrypted_data,
+        Err(e) => return ApiError::internal(e),
+    };
+    ApiError::VersionConflict {
+        status,
+        current_version,
+        details: json!({
+            "expected_version": expected.value(),
+            "current_version": current_version,
+            "current": encrypted_data,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn if_match_takes_precedence_over_the_body() {
+        assert!(matches!(expected_version(&if_match("\"3\""), Some(7)), Ok(ExpectedVersion::IfMatch(3))));
+    }
+
+    #[test]
+    fn weak_and_unquoted_tags_are_accepted() {
+        assert!(matches!(expected_version(&if_match("W/\"4\""), None), Ok(ExpectedVersion::IfMatch(4))));
+        assert!(matches!(expected_version(&if_match(" 5 "), None), Ok(ExpectedVersion::IfMatch(5))));
+    }
+
+    #[test]
+    fn a_malformed_if_match_is_rejected_even_with_a_body_version() {
+        assert!(matches!(expected_version(&if_match("\"abc\""), Some(7)), Err(ApiError::BadRequest(_))));
+    }
+
+    #[test]
+    fn the_body_version_is_used_without_if_match() {
+        assert!(matches!(expected_version(&HeaderMap::new(), Some(7)), Ok(ExpectedVersion::Body(7))));
+    }
+
+    #[test]
+    fn a_missing_version_is_a_precondition_failure() {
+        assert!(matches!(expected_version(&HeaderMap::new(), None), Err(ApiError::PreconditionRequired(_))));
+    }
+}
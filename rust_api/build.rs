@@ -0,0 +1,31 @@
+use std::{env, process::Command};
+
+// Stamps the binary with the commit it was built from, for GET /version.
+// Set GIT_COMMIT to override, e.g. when building outside a git checkout.
+fn main() {
+    let commit = env::var("GIT_COMMIT").ok().or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_string())
+    });
+    println!("cargo:rustc-env=GIT_COMMIT={}", commit.unwrap_or_else(|| "unknown".to_string()));
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs");
+
+    compile_protos();
+}
+
+// The gRPC stubs, built with a vendored protoc so no system install is needed.
+fn compile_protos() {
+    let mut config = prost_build::Config::new();
+    config.protoc_executable(protoc_bin_vendored::protoc_bin_path().expect("no vendored protoc for this platform"));
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_with_config(config, &["proto/employees.proto"], &["proto"])
+        .expect("failed to compile proto/employees.proto");
+}
@@ -0,0 +1,53 @@
+// audit/handlers.rs
+use axum::extract::{State, Json};
+use std::sync::Arc;
+use serde_json::{Value, to_value};
+
+use employee_core::audit::{AuditQuery, AuditRecord};
+use employee_core::auth::Claims;
+use employee_core::error::ApiError;
+use employee_core::db::AppState;
+use employee_core::validation::parse_request;
+use employee_core::sodium::sodium_crypto::{encrypt_json, get_key};
+use sqlx::query_as;
+use tracing::{error, instrument};
+
+// Audit history queries using POST, admin only
+pub async fn audit_query_handler(
+    claims: Claims,
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<String>, ApiError> {
+    claims.require_admin()?;
+    let request: AuditQuery = parse_request(payload)?;
+
+    match request {
+        AuditQuery::EmployeeHistory { emp_no, limit } => employee_history(&state, emp_no, limit.clamp(1, 500)).await,
+    }
+}
+
+// Every recorded change to the employee and to their dept_emp, dept_manager, title
+// and salary rows, newest first.
+#[instrument(name = "db.employee_history", skip_all, fields(emp_no = emp_no))]
+async fn employee_history(state: &Arc<AppState>, emp_no: i32, limit: i64) -> Result<Json<String>, ApiError> {
+    let query_str = "SELECT * FROM audit_log WHERE emp_no = ? ORDER BY id DESC LIMIT ?";
+
+    let records = query_as::<_, AuditRecord>(query_str)
+        .bind(emp_no)
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await;
+
+    match records {
+        Ok(data) => {
+            let json_data = to_value(data).map_err(ApiError::internal)?;
+            let key = get_key();
+            let encrypted_data = encrypt_json(&json_data, &key).map_err(ApiError::internal)?;
+            Ok(Json(encrypted_data))
+        }
+        Err(e) => {
+            error!(error = ?e, "Error fetching audit history");
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// audit/routes.rs
+use axum::{
+    routing::post,
+    Router,
+    extract::State,
+    Json,
+};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::audit::handlers::audit_query_handler;
+use employee_core::auth::Claims;
+use serde_json::Value;
+
+pub fn create_audit_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(move |claims: Claims, state: State<Arc<AppState>>, Json(payload): Json<Value>| audit_query_handler(claims, state, Json(payload))))
+        .with_state(app_state)
+}
@@ -0,0 +1,150 @@
+// batch/handlers.rs
+use axum::{extract::Json, http::HeaderMap};
+use serde::Serialize;
+use serde_json::{to_value, Value};
+use sqlx::{MySql, Transaction};
+use tracing::{info, instrument};
+
+use employee_core::batch::models::{BatchOperation, BatchRequest, BatchResult, OperationResult, OperationStatus};
+use employee_core::error::{ApiError, ErrorBody};
+use employee_core::repository::{crud, CrudAction, CrudContext, Resource};
+use employee_core::validation::{parse_request, Validate, ValidationErrors};
+use employee_core::versioning::expected_version;
+
+// Runs every operation in one transaction and reports each one. A failed operation is not an
+// HTTP error: the response is 200 with committed=false and the failure in its result, so the
+// client can tell which step broke. Requests that are malformed as a whole are still 4xx.
+#[instrument(name = "batch", skip_all)]
+pub async fn batch_handler(
+    ctx: CrudContext,
+    Json(payload): Json<Value>,
+) -> Result<Json<String>, ApiError> {
+    let actions = action_names(&payload);
+    let request: BatchRequest = parse_request(payload)?;
+    request.validate()?;
+
+    // Map everything up front so an unsupported action fails before anything is written.
+    let mut errors = ValidationErrors::default();
+    let operations = request.operations.into_iter().enumerate().filter_map(|(index, operation)| {
+        let resource = operation.resource();
+        let action = BatchAction::from_operation(operation);
+        if action.is_none() {
+            errors.add(&format!("operations[{}].action", index), format!("{} is not supported in a batch", actions[index]));
+        }
+        action.map(|action| (resource, action))
+    }).collect::<Vec<_>>();
+    errors.into_result()?;
+
+    let mut results = Vec::with_capacity(operations.len());
+    let mut tx = ctx.state.db.begin().await?;
+    let mut failed = false;
+    for (index, (resource, action)) in operations.into_iter().enumerate() {
+        let mut result = OperationResult {
+            index,
+            resource: resource.to_string(),
+            action: actions[index].clone(),
+            status: OperationStatus::Skipped,
+            result: None,
+            error: None,
+        };
+        if !failed {
+            match action.run(&mut tx, &ctx).await {
+                Ok(value) => {
+                    result.status = OperationStatus::Ok;
+                    result.result = Some(value);
+                }
+                Err(e) => {
+                    result.status = OperationStatus::Failed;
+                    result.error = Some(ErrorBody::from(&e));
+                    failed = true;
+                }
+            }
+        }
+        results.push(result);
+    }
+
+    if failed {
+        tx.rollback().await?;
+        for result in results.iter_mut().filter(|result| result.status == OperationStatus::Ok) {
+            result.status = OperationStatus::RolledBack;
+        }
+    } else {
+        tx.commit().await?;
+    }
+    info!(operations = results.len(), committed = !failed, "Batch finished");
+
+    crud::encrypted(&BatchResult { committed: !failed, results })
+}
+
+// The "action" of each operation as the client sent it, for the results.
+fn action_names(payload: &Value) -> Vec<String> {
+    payload.get("operations").and_then(Value::as_array).into_iter().flatten()
+        .map(|operation| operation.get("action").and_then(Value::as_str).unwrap_or_default().to_string())
+        .collect()
+}
+
+// A batch operation resolved to its resource's generic CRUD action.
+enum BatchAction {
+    Employees(CrudAction<employee_core::employees::models::Employee>),
+    Departments(CrudAction<employee_core::departments::models::Department>),
+    DeptEmp(CrudAction<employee_core::dept_emp::models::DeptEmp>),
+    DeptManager(CrudAction<employee_core::dept_manager::models::DeptManager>),
+    Titles(CrudAction<employee_core::titles::models::Title>),
+    Salaries(CrudAction<employee_core::salaries::models::Salary>),
+}
+
+impl BatchAction {
+    // None for read_all, the department summary and the workflow actions (terminate, rehire,
+    // salary adjustments), which manage their own transactions.
+    fn from_operation(operation: BatchOperation) -> Option<BatchAction> {
+        Some(match operation {
+            BatchOperation::Employees(request) => BatchAction::Employees(single_row(request.into_action().ok()?)?),
+            BatchOperation::Departments(request) => BatchAction::Departments(single_row(request.into_action().ok()?)?),
+            BatchOperation::DeptEmp(request) => BatchAction::DeptEmp(single_row(request.into_action())?),
+            BatchOperation::DeptManager(request) => BatchAction::DeptManager(single_row(request.into_action())?),
+            BatchOperation::Titles(request) => BatchAction::Titles(single_row(request.into_action())?),
+            BatchOperation::Salaries(request) => BatchAction::Salaries(single_row(request.into_action().ok()?)?),
+        })
+    }
+
+    async fn run(self, tx: &mut Transaction<'_, MySql>, ctx: &CrudContext) -> Result<Value, ApiError> {
+        match self {
+            BatchAction::Employees(action) => run_action(tx, ctx, action).await,
+            BatchAction::Departments(action) => run_action(tx, ctx, action).await,
+            BatchAction::DeptEmp(action) => run_action(tx, ctx, action).await,
+            BatchAction::DeptManager(action) => run_action(tx, ctx, action).await,
+            BatchAction::Titles(action) => run_action(tx, ctx, action).await,
+            BatchAction::Salaries(action) => run_action(tx, ctx, action).await,
+        }
+    }
+}
+
+fn single_row<R: Resource>(action: CrudAction<R>) -> Option<CrudAction<R>> {
+    (!matches!(action, CrudAction::ReadAll { .. })).then_some(action)
+}
+
+// Same checks and audit entries as the action's own endpoint, inside the batch transaction.
+// Updates take their version from the operation body: one If-Match header cannot cover a batch.
+async fn run_action<R: Resource>(tx: &mut Transaction<'_, MySql>, ctx: &CrudContext, action: CrudAction<R>) -> Result<Value, ApiError> {
+    match action {
+        CrudAction::Create(row) => {
+            row.validate()?;
+            json(&crud::create_in(tx, &ctx.audit, row).await?)
+        }
+        CrudAction::Read { key, include_deleted } => json(&crud::read_in::<R>(tx, &ctx.claims, key, include_deleted).await?),
+        CrudAction::Update { key, version, mut row } => {
+            let expected = expected_version(&HeaderMap::new(), version)?;
+            row.set_key(key.clone());
+            row.validate()?;
+            json(&crud::update_in(tx, &ctx.audit, key, expected, row).await?)
+        }
+        CrudAction::Delete(key) => json(&crud::delete_in::<R>(tx, &ctx.audit, key).await?),
+        CrudAction::Restore(key) => json(&crud::restore_in::<R>(tx, &ctx.audit, key).await?),
+        CrudAction::Purge(key) => json(&crud::purge_in::<R>(tx, &ctx.claims, &ctx.audit, key).await?),
+        CrudAction::ReadAll { .. } => Err(ApiError::bad_request("read_all is not supported in a batch")),
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> Result<Value, ApiError> {
+    to_value(value).map_err(ApiError::internal)
+}
@@ -0,0 +1,11 @@
+// batch/routes.rs
+use axum::{routing::post, Router};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::batch::handlers::batch_handler;
+
+pub fn create_batch_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(batch_handler))
+        .with_state(app_state)
+}
@@ -0,0 +1,89 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use employee_core::config::{ApiRouter, Config, LogFormat};
+use employee_core::import::models::{ImportMode, ImportResource};
+
+// Command-line flags, the last configuration layer. Anything not given here keeps the value
+// from the config file or environment.
+#[derive(Debug, Parser)]
+#[command(version, about = "Employee database API server")]
+pub struct Cli {
+    #[arg(long, help = "TOML config file (default: $APP_CONFIG, then ./config.toml if present)")]
+    pub config: Option<PathBuf>,
+    #[arg(long, help = "Address to listen on")]
+    pub host: Option<String>,
+    #[arg(long, help = "Port to listen on")]
+    pub port: Option<u16>,
+    #[arg(long, help = "Port for the gRPC server")]
+    pub grpc_port: Option<u16>,
+    #[arg(long = "cors-origin", help = "Allowed CORS origin; repeat for several")]
+    pub cors_origins: Vec<String>,
+    #[arg(long, help = "MySQL connection URL")]
+    pub database_url: Option<String>,
+    #[arg(long, help = "Maximum pooled database connections")]
+    pub max_connections: Option<u32>,
+    #[arg(long, help = "Requests per user before 429")]
+    pub rate_limit_requests: Option<i32>,
+    #[arg(long, help = "Resource routes to serve: actions or legacy")]
+    pub router: Option<ApiRouter>,
+    #[arg(long, help = "Log output: json or text")]
+    pub log_format: Option<LogFormat>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+// Without a subcommand the binary runs the server.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(about = "Import a CSV file into employees, salaries, titles or dept_emp, then exit")]
+    Import(ImportArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    #[arg(help = "Table to import into: employees, salaries, titles or dept_emp")]
+    pub resource: ImportResource,
+    #[arg(help = "CSV file with a header row naming the columns")]
+    pub file: PathBuf,
+    #[arg(long, default_value = "all_or_nothing", help = "all_or_nothing or best_effort")]
+    pub mode: ImportMode,
+    #[arg(long, help = "Check every row against the database, then roll back")]
+    pub dry_run: bool,
+    #[arg(long, default_value = "import-cli", help = "Actor recorded in the audit log")]
+    pub actor: String,
+    #[arg(long, help = "Write the JSON report here instead of stdout")]
+    pub report: Option<PathBuf>,
+}
+
+impl Cli {
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(host) = &self.host {
+            config.listener.host = host.clone();
+        }
+        if let Some(port) = self.port {
+            config.listener.port = port;
+        }
+        if let Some(port) = self.grpc_port {
+            config.grpc.port = port;
+        }
+        if !self.cors_origins.is_empty() {
+            config.cors.allowed_origins = self.cors_origins.clone();
+        }
+        if let Some(url) = &self.database_url {
+            config.database.url = url.clone();
+        }
+        if let Some(max_connections) = self.max_connections {
+            config.database.max_connections = max_connections;
+        }
+        if let Some(requests) = self.rate_limit_requests {
+            config.rate_limit.requests = requests;
+        }
+        if let Some(router) = self.router {
+            config.router = router;
+        }
+        if let Some(format) = self.log_format {
+            config.log.format = format;
+        }
+    }
+}
@@ -1,136 +1,157 @@
 // Departments functions
-use axum::{extract::{State, Json}, http::StatusCode};
+use axum::{
+    extract::{rejection::QueryRejection, Json, Query},
+    response::{IntoResponse, Response},
+};
+use chrono::{Local, NaiveDate};
+use serde_json::Value;
+use sqlx::{query_as, FromRow, MySql, Transaction};
+use std::collections::HashMap;
 use std::sync::Arc;
-use serde_json::{Value, from_value, to_value};
+use tracing::instrument;
 
-use crate::db::AppState;
-use crate::departments::models::Department;
-use crate::sodium::sodium_crypto::{encrypt_json, get_key};
-use crate::auth::Claims;
-use sqlx::{query, query_as};
+use employee_core::error::ApiError;
+use employee_core::db::{AppState, IN_EFFECT};
+use employee_core::departments::models::{Department, DepartmentManagerSummary, DepartmentRequest, DepartmentSummary, SummaryQuery, TitleCount};
+use employee_core::repository::{crud::encrypted, execute, rest::{self, is_action_request}, CrudContext};
+use employee_core::validation::{parse_request, Validate};
 
 // CRUD operations using POST
 pub async fn department_crud_handler(
-    _claims: Claims,
-    State(state): State<Arc<AppState>>,
+    ctx: CrudContext,
     Json(payload): Json<Value>,
-) -> Result<Json<String>, StatusCode> {
-    let action = payload["action"].as_str().ok_or(StatusCode::BAD_REQUEST)?;
-
-    match action {
-        "create" => create_department(&state, payload["department"].clone()).await,
-        "read_all" => read_all_departments(&state).await,
-        "read" => read_department(&state, payload["dept_no"].as_str().ok_or(StatusCode::BAD_REQUEST)?.to_string()).await,
-        "update" => update_department(&state, payload["dept_no"].as_str().ok_or(StatusCode::BAD_REQUEST)?.to_string(), payload["department"].clone()).await,
-        "delete" => delete_department(&state, payload["dept_no"].as_str().ok_or(StatusCode::BAD_REQUEST)?.to_string()).await,
-        _ => Err(StatusCode::BAD_REQUEST),
+) -> Result<Response, ApiError> {
+    if !is_action_request(&payload) {
+        return rest::create::<Department>(&ctx, payload).await;
     }
-}
-
-async fn create_department(state: &Arc<AppState>, department_value: Value) -> Result<Json<String>, StatusCode> {
-    let new_department: Department = from_value(department_value).map_err(|_| StatusCode::BAD_REQUEST)?;
-
-    let query_str = "INSERT INTO departments (dept_no, dept_name) VALUES (?, ?)";
 
-    let result = query(query_str)
-        .bind(&new_department.dept_no)
-        .bind(&new_department.dept_name)
-        .execute(&state.db)
-        .await;
+    let action = match parse_request(payload)? {
+        DepartmentRequest::Summary { dept_no, as_of } => return Ok(encrypted(&summarize_departments(&ctx.state, SummaryQuery { dept_no, as_of }).await?)?.into_response()),
+        request => request.into_action()?,
+    };
 
-    match result {
-        Ok(_) => {
-            let json_data = to_value(new_department).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error creating department: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+    execute(&ctx, action).await
 }
 
-async fn read_all_departments(state: &Arc<AppState>) -> Result<Json<String>, StatusCode> {
-    let query_str = "SELECT * FROM departments LIMIT 10";
-
-    let departments = query_as::<_, Department>(query_str)
-        .fetch_all(&state.db)
-        .await;
+// GET /departments/summary?dept_no=d005&as_of=2000-01-01
+pub async fn department_summary_handler(
+    ctx: CrudContext,
+    params: Result<Query<SummaryQuery>, QueryRejection>,
+) -> Result<Json<String>, ApiError> {
+    let Query(params) = params?;
+    encrypted(&summarize_departments(&ctx.state, params).await?)
+}
 
-    match departments {
-        Ok(data) => {
-            let json_data = to_value(data).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error fetching departments: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+#[derive(FromRow)]
+struct DepartmentCounts {
+    dept_no: String,
+    headcount: i64,
+    average_salary: Option<f64>,
 }
 
-async fn read_department(state: &Arc<AppState>, dept_no: String) -> Result<Json<String>, StatusCode> {
-    let query_str = "SELECT * FROM departments WHERE dept_no = ?";
-    let department = query_as::<_, Department>(query_str)
-        .bind(dept_no)
-        .fetch_one(&state.db)
-        .await;
-
-    match department {
-        Ok(data) => {
-            let json_data = to_value(data).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(_) => Err(StatusCode::NOT_FOUND),
-    }
+#[derive(FromRow)]
+struct DepartmentManagerRow {
+    dept_no: String,
+    #[sqlx(flatten)]
+    manager: DepartmentManagerSummary,
 }
 
-async fn update_department(state: &Arc<AppState>, dept_no: String, department_value: Value) -> Result<Json<String>, StatusCode> {
-    let mut updated_department: Department = from_value(department_value).map_err(|_| StatusCode::BAD_REQUEST)?;
+#[derive(FromRow)]
+struct DepartmentTitleRow {
+    dept_no: String,
+    #[sqlx(flatten)]
+    title: TitleCount,
+}
 
-    updated_department.dept_no = dept_no.clone(); // Use dept_no from path parameter
+// Every department (or the one asked for), in dept_no order. The four queries share one
+// transaction so the figures come from the same snapshot.
+#[instrument(name = "db.department_summary", skip_all, fields(dept_no = ?params.dept_no, as_of = ?params.as_of))]
+pub async fn summarize_departments(state: &Arc<AppState>, params: SummaryQuery) -> Result<Vec<DepartmentSummary>, ApiError> {
+    params.validate()?;
+    let as_of = params.as_of.unwrap_or_else(|| Local::now().date_naive());
+    let dept_no = params.dept_no.as_deref();
+
+    let mut tx = state.db.begin().await?;
+
+    let departments = fetch_by_department::<(String, String)>(&mut tx, as_of, dept_no, &format!(
+        "SELECT dept_no, dept_name FROM departments WHERE deleted_at IS NULL{} ORDER BY dept_no",
+        dept_filter("departments", dept_no),
+    ), 0).await?;
+    if departments.is_empty() && dept_no.is_some() {
+        return Err(ApiError::not_found("Department not found"));
+    }
 
-    let query_str = "UPDATE departments SET dept_name = ? WHERE dept_no = ?";
+    let mut managers = HashMap::new();
+    for row in fetch_by_department::<DepartmentManagerRow>(&mut tx, as_of, dept_no, &format!(
+        "SELECT dm.dept_no, e.emp_no, e.first_name, e.last_name, dm.from_date \
+         FROM dept_manager dm INNER JOIN employees e ON e.emp_no = dm.emp_no AND e.deleted_at IS NULL \
+         WHERE dm.deleted_at IS NULL AND dm.{}{} \
+         ORDER BY dm.dept_no, dm.from_date",
+        IN_EFFECT, dept_filter("dm", dept_no),
+    ), 2).await? {
+        // Ordered by from_date, so the latest appointment wins.
+        managers.insert(row.dept_no, row.manager);
+    }
 
-    let result = query(query_str)
-        .bind(&updated_department.dept_name)
-        .bind(dept_no)
-        .execute(&state.db)
-        .await;
+    // Salaries and titles are matched on as_of too, so one employee counts once per department.
+    let counts = fetch_by_department::<DepartmentCounts>(&mut tx, as_of, dept_no, &format!(
+        "SELECT de.dept_no, COUNT(DISTINCT de.emp_no) AS headcount, CAST(ROUND(AVG(s.salary), 2) AS DOUBLE) AS average_salary \
+         FROM dept_emp de \
+         INNER JOIN employees e ON e.emp_no = de.emp_no AND e.deleted_at IS NULL \
+         LEFT JOIN salaries s ON s.emp_no = de.emp_no AND s.deleted_at IS NULL AND s.{} \
+         WHERE de.deleted_at IS NULL AND de.{}{} \
+         GROUP BY de.dept_no",
+        IN_EFFECT, IN_EFFECT, dept_filter("de", dept_no),
+    ), 4).await?;
+    let mut counts = counts.into_iter().map(|row| (row.dept_no.clone(), row)).collect::<HashMap<_, _>>();
+
+    let mut titles: HashMap<String, Vec<TitleCount>> = HashMap::new();
+    for row in fetch_by_department::<DepartmentTitleRow>(&mut tx, as_of, dept_no, &format!(
+        "SELECT de.dept_no, t.title, COUNT(DISTINCT t.emp_no) AS employees \
+         FROM dept_emp de \
+         INNER JOIN employees e ON e.emp_no = de.emp_no AND e.deleted_at IS NULL \
+         INNER JOIN titles t ON t.emp_no = de.emp_no AND t.deleted_at IS NULL AND t.{} \
+         WHERE de.deleted_at IS NULL AND de.{}{} \
+         GROUP BY de.dept_no, t.title \
+         ORDER BY de.dept_no, employees DESC, t.title",
+        IN_EFFECT, IN_EFFECT, dept_filter("de", dept_no),
+    ), 4).await? {
+        titles.entry(row.dept_no).or_default().push(row.title);
+    }
 
-    match result {
-        Ok(_) => {
-            let json_data = to_value(updated_department).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error updating department: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    tx.commit().await?;
+
+    Ok(departments.into_iter().map(|(dept_no, dept_name)| {
+        let counts = counts.remove(&dept_no);
+        DepartmentSummary {
+            manager: managers.remove(&dept_no),
+            headcount: counts.as_ref().map_or(0, |counts| counts.headcount),
+            average_salary: counts.and_then(|counts| counts.average_salary),
+            titles: titles.remove(&dept_no).unwrap_or_default(),
+            as_of,
+            dept_no,
+            dept_name,
         }
-    }
+    }).collect())
 }
 
-async fn delete_department(state: &Arc<AppState>, dept_no: String) -> Result<Json<String>, StatusCode> {
-    let query_str = "DELETE FROM departments WHERE dept_no = ?";
-
-    let result = query(query_str)
-        .bind(dept_no)
-        .execute(&state.db)
-        .await;
+// Binds as_of once per IN_EFFECT placeholder (`dates`), then the optional dept_no.
+async fn fetch_by_department<T>(tx: &mut Transaction<'_, MySql>, as_of: NaiveDate, dept_no: Option<&str>, sql: &str, dates: usize) -> Result<Vec<T>, ApiError>
+where
+    T: for<'r> FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
+{
+    let query = (0..dates).fold(query_as::<_, T>(sql), |query, _| query.bind(as_of));
+    let query = match dept_no {
+        Some(dept_no) => query.bind(dept_no),
+        None => query,
+    };
+    Ok(query.fetch_all(&mut **tx).await?)
+}
 
-    match result {
-        Ok(_) => Ok(Json(String::from("Department deleted successfully"))),
-        Err(e) => {
-            eprintln!("Error deleting department: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+// "AND dept_no = ?" when one department was asked for; its value is bound last.
+fn dept_filter(table: &str, dept_no: Option<&str>) -> String {
+    match dept_no {
+        Some(_) => format!(" AND {}.dept_no = ?", table),
+        None => String::new(),
     }
-}
\ No newline at end of file
+}
@@ -1,18 +1,17 @@
 // route.rs
 use axum::{
-    routing::post,
+    routing::{get, post},
     Router,
-    extract::State,
-    Json,
 };
 use std::sync::Arc;
-use crate::db::AppState;
-use crate::departments::handlers::department_crud_handler;
-use crate::auth::Claims;
-use serde_json::Value;
+use employee_core::db::AppState;
+use employee_core::repository::rest_routes;
+use employee_core::departments::models::Department;
+use crate::departments::handlers::{department_crud_handler, department_summary_handler};
 
 pub fn create_department_routes(app_state: Arc<AppState>) -> Router {
-    Router::new()
-        .route("/", post(move |claims: Claims, state: State<Arc<AppState>>, Json(payload): Json<Value>| department_crud_handler(claims, state, Json(payload))))
+    rest_routes::<Department>()
+        .route("/", post(department_crud_handler))
+        .route("/summary", get(department_summary_handler))
         .with_state(app_state)
 }
\ No newline at end of file
@@ -2,17 +2,15 @@
 use axum::{
     routing::post,
     Router,
-    extract::State,
-    Json,
 };
 use std::sync::Arc;
-use crate::db::AppState;
+use employee_core::db::AppState;
+use employee_core::repository::rest_routes;
+use employee_core::dept_manager::models::DeptManager;
 use crate::dept_manager::handlers::dept_manager_crud_handler;
-use crate::auth::Claims;
-use serde_json::Value;
 
 pub fn create_dept_manager_routes(app_state: Arc<AppState>) -> Router {
-    Router::new()
-        .route("/", post(move |claims: Claims, state: State<Arc<AppState>>, Json(payload): Json<Value>| dept_manager_crud_handler(claims, state, Json(payload))))
+    rest_routes::<DeptManager>()
+        .route("/", post(dept_manager_crud_handler))
         .with_state(app_state)
 }
\ No newline at end of file
@@ -1,158 +1,226 @@
 // Employees functions
-use axum::{extract::{State, Json}, http::StatusCode};
+use axum::{extract::Json, response::{IntoResponse, Response}};
 use std::sync::Arc;
 
-use crate::db::AppState;
-use crate::employees::models::Employee;
-use crate::auth::Claims;
-use crate::sodium::sodium_crypto::{encrypt_json, get_key};
+use employee_core::error::ApiError;
+use employee_core::repository::{crud::{self, encrypted}, execute, rest::{self, is_action_request}, CrudContext};
+use employee_core::db::{AppState, initial_row_version, open_end_date};
+use employee_core::dept_emp::models::DeptEmp;
+use employee_core::employees::models::{Employee, EmployeeRequest, RehireEmployee, RehireResult, TerminateEmployee, Termination, TerminationResult};
+use employee_core::salaries::models::Salary;
+use employee_core::titles::models::Title;
+use employee_core::audit::AuditContext;
+use employee_core::validation::{parse_request, Validate};
 
-use serde_json::{Value, from_value, to_value};
-use sqlx::{query, query_as};
+use serde_json::{json, Value};
+use sqlx::{query, query_as, query_scalar, MySql, Transaction};
+use tracing::{error, instrument};
 
 // CRUD operations using POST
 pub async fn employee_crud_handler(
-    _claims: Claims,
-    State(state): State<Arc<AppState>>,
+    ctx: CrudContext,
     Json(payload): Json<Value>,
-) -> Result<Json<String>, StatusCode> {
-    let action = payload["action"].as_str().ok_or(StatusCode::BAD_REQUEST)?;
-
-    match action {
-        "create" => create_employee(&state, payload["employee"].clone()).await,
-        "read_all" => read_all_employees(&state).await,
-        "read" => read_employee(&state, payload["emp_no"].as_i64().ok_or(StatusCode::BAD_REQUEST)? as i32).await,
-        "update" => update_employee(&state, payload["emp_no"].as_i64().ok_or(StatusCode::BAD_REQUEST)? as i32, payload["employee"].clone()).await,
-        "delete" => delete_employee(&state, payload["emp_no"].as_i64().ok_or(StatusCode::BAD_REQUEST)? as i32).await,
-        _ => Err(StatusCode::BAD_REQUEST),
+) -> Result<Response, ApiError> {
+    if !is_action_request(&payload) {
+        return rest::create::<Employee>(&ctx, payload).await;
     }
+
+    let action = match parse_request(payload)? {
+        EmployeeRequest::Terminate { emp_no, termination } => return Ok(encrypted(&terminate_employee(&ctx.state, &ctx.audit, emp_no, termination).await?)?.into_response()),
+        EmployeeRequest::Rehire { emp_no, rehire } => return Ok(encrypted(&rehire_employee(&ctx.state, &ctx.audit, emp_no, rehire).await?)?.into_response()),
+        request => request.into_action()?,
+    };
+
+    execute(&ctx, action).await
 }
 
-async fn create_employee(state: &Arc<AppState>, employee_value: Value) -> Result<Json<String>, StatusCode> {
-    let new_employee: Employee = from_value(employee_value).map_err(|_| StatusCode::BAD_REQUEST)?;
-
-    let query_str = "INSERT INTO employees (emp_no, birth_date, first_name, last_name, gender, hire_date) VALUES (?, ?, ?, ?, ?, ?)";
-
-    let result = query(query_str)
-        .bind(new_employee.emp_no)
-        .bind(&new_employee.birth_date)
-        .bind(&new_employee.first_name)
-        .bind(&new_employee.last_name)
-        .bind(&new_employee.gender)
-        .bind(&new_employee.hire_date)
-        .execute(&state.db)
-        .await;
-
-    match result {
-        Ok(_) => {
-            let json_data = to_value(new_employee).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error creating employee: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+// Ends every open dept_emp, dept_manager, title and salary interval at the termination date.
+#[instrument(name = "db.terminate_employee", skip_all, fields(emp_no = emp_no))]
+pub async fn terminate_employee(state: &Arc<AppState>, audit: &AuditContext, emp_no: i32, request: TerminateEmployee) -> Result<TerminationResult, ApiError> {
+    request.validate()?;
+
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!(error = ?e, "Error starting termination");
+        ApiError::from(e)
+    })?;
+
+    lock_live_employee(&mut tx, emp_no).await?;
+
+    if open_termination(&mut tx, emp_no).await?.is_some() {
+        return Err(ApiError::conflict("Employee is already terminated"));
     }
-}
 
-async fn read_all_employees(state: &Arc<AppState>) -> Result<Json<String>, StatusCode> {
-    let query_str = "SELECT * FROM employees LIMIT 10";
-
-    let employees = query_as::<_, Employee>(query_str)
-        .fetch_all(&state.db)
-        .await;
-
-    match employees {
-        Ok(data) => {
-            let json_data = to_value(data).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error fetching employees: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    // An interval that only starts after the termination date can't be closed by it.
+    // Soft-deleted intervals are left alone so a later restore finds them as they were.
+    let query_str = "SELECT \
+        (SELECT COUNT(*) FROM dept_emp WHERE emp_no = ? AND to_date = ? AND from_date > ? AND deleted_at IS NULL) + \
+        (SELECT COUNT(*) FROM dept_manager WHERE emp_no = ? AND to_date = ? AND from_date > ? AND deleted_at IS NULL) + \
+        (SELECT COUNT(*) FROM titles WHERE emp_no = ? AND (to_date = ? OR to_date IS NULL) AND from_date > ? AND deleted_at IS NULL) + \
+        (SELECT COUNT(*) FROM salaries WHERE emp_no = ? AND to_date = ? AND from_date > ? AND deleted_at IS NULL)";
+    let mut later_intervals = query_scalar::<_, i64>(query_str);
+    for _ in 0..4 {
+        later_intervals = later_intervals.bind(emp_no).bind(open_end_date()).bind(request.termination_date);
+    }
+    if later_intervals.fetch_one(&mut *tx).await.map_err(ApiError::from)? > 0 {
+        return Err(ApiError::bad_request("Employee has intervals starting after the termination date"));
     }
-}
 
-async fn read_employee(state: &Arc<AppState>, emp_no: i32) -> Result<Json<String>, StatusCode> {
-    let query_str = "SELECT * FROM employees WHERE emp_no = ?";
-    let employee = query_as::<_, Employee>(query_str)
-        .bind(emp_no)
-        .fetch_one(&state.db)
-        .await;
-
-    match employee {
-        Ok(data) => {
-            let json_data = to_value(data).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(_) => Err(StatusCode::NOT_FOUND),
+    let close_queries = [
+        "UPDATE dept_emp SET to_date = ?, row_version = row_version + 1 WHERE emp_no = ? AND to_date = ? AND deleted_at IS NULL",
+        "UPDATE dept_manager SET to_date = ?, row_version = row_version + 1 WHERE emp_no = ? AND to_date = ? AND deleted_at IS NULL",
+        "UPDATE titles SET to_date = ?, row_version = row_version + 1 WHERE emp_no = ? AND (to_date = ? OR to_date IS NULL) AND deleted_at IS NULL",
+        "UPDATE salaries SET to_date = ?, row_version = row_version + 1 WHERE emp_no = ? AND to_date = ? AND deleted_at IS NULL",
+    ];
+    let mut closed = [0u64; 4];
+    for (count, query_str) in closed.iter_mut().zip(close_queries) {
+        *count = query(query_str)
+            .bind(request.termination_date)
+            .bind(emp_no)
+            .bind(open_end_date())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(error = ?e, "Error closing intervals");
+                ApiError::from(e)
+            })?
+            .rows_affected();
     }
-}
 
-async fn update_employee(state: &Arc<AppState>, emp_no: i32, employee_value: Value) -> Result<Json<String>, StatusCode> {
-    println!("Updating employee with emp_no: {}", emp_no);
+    query("INSERT INTO terminations (emp_no, termination_date, reason) VALUES (?, ?, ?)")
+        .bind(emp_no)
+        .bind(request.termination_date)
+        .bind(&request.reason)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error recording termination");
+            ApiError::from(e)
+        })?;
+
+    let result = TerminationResult {
+        termination: Termination {
+            emp_no,
+            termination_date: request.termination_date,
+            reason: request.reason,
+            rehire_date: None,
+        },
+        closed_dept_emp: closed[0],
+        closed_dept_manager: closed[1],
+        closed_titles: closed[2],
+        closed_salaries: closed[3],
+    };
+    audit.record(&mut tx, "terminate", "employees", json!({"emp_no": emp_no}), None, Some(&result)).await?;
+
+    tx.commit().await.map_err(|e| {
+        error!(error = ?e, "Error committing termination");
+        ApiError::from(e)
+    })?;
+
+    Ok(result)
+}
 
-    let mut updated_employee: Employee = match from_value(employee_value.clone()) {
-        Ok(employee) => employee,
-        Err(e) => {
-            eprintln!("Error parsing employee_value: {:?}", e);
-            return Err(StatusCode::BAD_REQUEST);
-        }
+// Opens new department, title and salary intervals for a terminated employee.
+// Earlier intervals are left untouched so the full history is kept.
+#[instrument(name = "db.rehire_employee", skip_all, fields(emp_no = emp_no))]
+pub async fn rehire_employee(state: &Arc<AppState>, audit: &AuditContext, emp_no: i32, request: RehireEmployee) -> Result<RehireResult, ApiError> {
+    request.validate()?;
+
+    let dept_emp = DeptEmp {
+        emp_no,
+        dept_no: request.dept_no,
+        from_date: request.rehire_date,
+        to_date: open_end_date(),
+        row_version: initial_row_version(),
+        deleted_at: None,
+    };
+    let title = Title {
+        emp_no,
+        title: request.title,
+        from_date: request.rehire_date,
+        to_date: Some(open_end_date()),
+        row_version: initial_row_version(),
+        deleted_at: None,
+    };
+    let salary = Salary {
+        emp_no,
+        salary: request.salary,
+        from_date: request.rehire_date,
+        to_date: open_end_date(),
+        row_version: initial_row_version(),
+        deleted_at: None,
     };
 
-    println!("Parsed updated_employee: {:?}", updated_employee);
+    dept_emp.validate()?;
+    title.validate()?;
+    salary.validate()?;
 
-    //Use emp_no from path parameter
-    updated_employee.emp_no = emp_no;
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!(error = ?e, "Error starting rehire");
+        ApiError::from(e)
+    })?;
 
-    let query_str = "UPDATE employees SET birth_date = ?, first_name = ?, last_name = ?, gender = ?, hire_date = ? WHERE emp_no = ?";
+    lock_live_employee(&mut tx, emp_no).await?;
 
-    let result = query(query_str)
-        .bind(&updated_employee.birth_date)
-        .bind(&updated_employee.first_name)
-        .bind(&updated_employee.last_name)
-        .bind(&updated_employee.gender)
-        .bind(&updated_employee.hire_date)
-        .bind(emp_no)
-        .execute(&state.db)
-        .await;
-
-    println!("Database update result: {:?}", result);
-
-    match result {
-        Ok(_) => {
-            let json_data = to_value(updated_employee).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error updating employee: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    // Only someone who is currently terminated can be rehired.
+    let termination = open_termination(&mut tx, emp_no).await?
+        .ok_or_else(|| ApiError::conflict("Employee is not terminated"))?;
+    if request.rehire_date <= termination.termination_date {
+        return Err(ApiError::bad_request("rehire_date must be after the termination date"));
     }
+
+    // Through the generic create so each new row gets its own audit entry.
+    let dept_emp = crud::create_in(&mut tx, audit, dept_emp).await?;
+    let title = crud::create_in(&mut tx, audit, title).await?;
+    let salary = crud::create_in(&mut tx, audit, salary).await?;
+
+    query("UPDATE terminations SET rehire_date = ? WHERE emp_no = ? AND termination_date = ?")
+        .bind(request.rehire_date)
+        .bind(emp_no)
+        .bind(termination.termination_date)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error recording rehire");
+            ApiError::from(e)
+        })?;
+
+    let result = RehireResult {
+        emp_no,
+        rehire_date: request.rehire_date,
+        dept_emp,
+        title,
+        salary,
+    };
+    audit.record(&mut tx, "rehire", "employees", json!({"emp_no": emp_no}), None, Some(&result)).await?;
+
+    tx.commit().await.map_err(|e| {
+        error!(error = ?e, "Error committing rehire");
+        ApiError::from(e)
+    })?;
+
+    Ok(result)
 }
 
-async fn delete_employee(state: &Arc<AppState>, emp_no: i32) -> Result<Json<String>, StatusCode> {
-    let query_str = "DELETE FROM employees WHERE emp_no = ?";
+// Locks the employee row; a soft-deleted employee is not found.
+async fn lock_live_employee(tx: &mut Transaction<'_, MySql>, emp_no: i32) -> Result<(), ApiError> {
+    query_scalar::<_, i32>("SELECT emp_no FROM employees WHERE emp_no = ? AND deleted_at IS NULL FOR UPDATE")
+        .bind(emp_no)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Employee not found"))?;
+    Ok(())
+}
 
-    let result = query(query_str)
+// The termination that hasn't been followed by a rehire yet, if any.
+async fn open_termination(tx: &mut Transaction<'_, MySql>, emp_no: i32) -> Result<Option<Termination>, ApiError> {
+    query_as::<_, Termination>("SELECT * FROM terminations WHERE emp_no = ? AND rehire_date IS NULL FOR UPDATE")
         .bind(emp_no)
-        .execute(&state.db)
-        .await;
-
-    match result {
-        Ok(_) => Ok(Json(String::from("Employee deleted successfully"))),
-        Err(e) => {
-            eprintln!("Error deleting employee: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
-}
\ No newline at end of file
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error fetching termination");
+            ApiError::from(e)
+        })
+}
+
@@ -0,0 +1,124 @@
+// Incremental writers for the export formats. Rows go in one at a time and take() hands back
+// the bytes that are ready, so only the current chunk (for Parquet, the current row group) is
+// ever held in memory.
+use arrow_json::reader::{Decoder, ReaderBuilder};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use axum::body::Bytes;
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use serde::Serialize;
+use serde_json::{to_value, Value};
+use std::{error::Error, sync::Arc};
+
+use employee_core::export::models::ExportFormat;
+use employee_core::repository::Resource;
+
+pub type EncodeError = Box<dyn Error + Send + Sync>;
+
+// Parquet row groups are flushed at this size; it bounds the writer's buffer.
+const ROW_GROUP_ROWS: usize = 65_536;
+
+pub struct Encoder {
+    columns: Vec<&'static str>,
+    writer: Writer,
+}
+
+enum Writer {
+    Csv(csv::Writer<Vec<u8>>),
+    Ndjson(Vec<u8>),
+    Parquet { decoder: Decoder, writer: ArrowWriter<Vec<u8>> },
+}
+
+// Every column of the table, in order, with the bookkeeping columns last.
+pub fn columns<R: Resource>() -> Vec<&'static str> {
+    R::COLUMNS.iter().copied().chain(["row_version", "deleted_at"]).collect()
+}
+
+// The csv writer keeps its output to itself until into_inner, so each chunk gets a fresh one.
+fn csv_writer() -> csv::Writer<Vec<u8>> {
+    csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new())
+}
+
+// The Arrow type of an export column, from the naming conventions of the employees schema.
+fn field(column: &'static str) -> Field {
+    let data_type = match column {
+        "emp_no" | "salary" | "row_version" => DataType::Int32,
+        "deleted_at" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        column if column.ends_with("_date") => DataType::Date32,
+        _ => DataType::Utf8,
+    };
+    // titles.to_date is the only nullable data column.
+    Field::new(column, data_type, matches!(column, "to_date" | "deleted_at"))
+}
+
+impl Encoder {
+    pub fn new<R: Resource>(format: ExportFormat) -> Result<Self, EncodeError> {
+        let columns = columns::<R>();
+        let writer = match format {
+            ExportFormat::Csv => {
+                let mut writer = csv_writer();
+                writer.write_record(&columns)?;
+                Writer::Csv(writer)
+            }
+            ExportFormat::Ndjson => Writer::Ndjson(Vec::new()),
+            ExportFormat::Parquet => {
+                let schema = Arc::new(Schema::new(columns.iter().copied().map(field).collect::<Vec<_>>()));
+                let properties = WriterProperties::builder()
+                    .set_compression(Compression::SNAPPY)
+                    .set_max_row_group_size(ROW_GROUP_ROWS)
+                    .build();
+                Writer::Parquet {
+                    decoder: ReaderBuilder::new(schema.clone()).build_decoder()?,
+                    writer: ArrowWriter::try_new(Vec::new(), schema, Some(properties))?,
+                }
+            }
+        };
+        Ok(Encoder { columns, writer })
+    }
+
+    pub fn push<R: Serialize>(&mut self, row: &R) -> Result<(), EncodeError> {
+        match &mut self.writer {
+            Writer::Csv(writer) => {
+                let row = to_value(row)?;
+                writer.write_record(self.columns.iter().map(|column| match row.get(column) {
+                    None | Some(Value::Null) => String::new(),
+                    Some(Value::String(value)) => value.clone(),
+                    Some(value) => value.to_string(),
+                }))?;
+            }
+            Writer::Ndjson(buffer) => {
+                serde_json::to_writer(&mut *buffer, row)?;
+                buffer.push(b'\n');
+            }
+            Writer::Parquet { decoder, .. } => decoder.serialize(std::slice::from_ref(row))?,
+        }
+        Ok(())
+    }
+
+    // The bytes encoded since the last call. Parquet only releases whole row groups.
+    pub fn take(&mut self) -> Result<Bytes, EncodeError> {
+        let bytes = match &mut self.writer {
+            Writer::Csv(writer) => std::mem::replace(writer, csv_writer()).into_inner().map_err(|e| e.into_error())?,
+            Writer::Ndjson(buffer) => std::mem::take(buffer),
+            Writer::Parquet { decoder, writer } => {
+                if let Some(batch) = decoder.flush()? {
+                    writer.write(&batch)?;
+                }
+                std::mem::take(writer.inner_mut())
+            }
+        };
+        Ok(Bytes::from(bytes))
+    }
+
+    // The rest of the output, including the Parquet footer.
+    pub fn finish(mut self) -> Result<Bytes, EncodeError> {
+        let bytes = self.take()?;
+        match self.writer {
+            Writer::Parquet { writer, .. } => {
+                let mut rest = bytes.to_vec();
+                rest.extend(writer.into_inner()?);
+                Ok(Bytes::from(rest))
+            }
+            _ => Ok(bytes),
+        }
+    }
+}
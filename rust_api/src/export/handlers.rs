@@ -0,0 +1,112 @@
+// export/handlers.rs
+use axum::{
+    body::Body,
+    extract::{rejection::{PathRejection, QueryRejection}, Path, Query},
+    http::header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::Response,
+};
+use futures::{stream, StreamExt};
+use std::io;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{error, info, info_span, Instrument};
+
+use employee_core::departments::models::Department;
+use employee_core::dept_emp::models::DeptEmp;
+use employee_core::dept_manager::models::DeptManager;
+use employee_core::employees::models::Employee;
+use employee_core::error::ApiError;
+use employee_core::export::models::{ExportQuery, ExportResource};
+use employee_core::repository::{stream_rows, CrudContext, Resource};
+use employee_core::salaries::models::Salary;
+use employee_core::titles::models::Title;
+use employee_core::validation::Validate;
+
+use crate::export::encode::Encoder;
+
+// Rows encoded per chunk sent to the client.
+const CHUNK_ROWS: usize = 1024;
+// Chunks buffered ahead of a slow client before the encoder waits.
+const CHUNK_BUFFER: usize = 4;
+
+// Full-table extracts, admin only. The body is written while the rows are read, so it is not
+// sealed like the other responses; a database error halfway aborts the body, which the client
+// sees as a truncated transfer.
+pub async fn export_handler(
+    ctx: CrudContext,
+    resource: Result<Path<ExportResource>, PathRejection>,
+    params: Result<Query<ExportQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    ctx.claims.require_admin()?;
+    let (Path(resource), Query(params)) = (resource?, params?);
+    params.validate()?;
+
+    match resource {
+        ExportResource::Employees => export::<Employee>(&ctx, &params).await,
+        ExportResource::Departments => export::<Department>(&ctx, &params).await,
+        ExportResource::DeptEmp => export::<DeptEmp>(&ctx, &params).await,
+        ExportResource::DeptManager => export::<DeptManager>(&ctx, &params).await,
+        ExportResource::Titles => export::<Title>(&ctx, &params).await,
+        ExportResource::Salaries => export::<Salary>(&ctx, &params).await,
+    }
+}
+
+async fn export<R: Resource>(ctx: &CrudContext, params: &ExportQuery) -> Result<Response, ApiError> {
+    let mut rows = stream_rows::<R>(ctx, params.row_filter::<R>()?)?;
+    let encoder = Encoder::new::<R>(params.format).map_err(ApiError::internal)?;
+    // Waiting for the first row means a query that cannot start (a busy pool, a bad filter)
+    // still gets a proper error response instead of an empty, aborted 200.
+    let first = rows.recv().await.transpose()?;
+    let (sender, receiver) = mpsc::channel(CHUNK_BUFFER);
+    let span = info_span!("export", table = R::TABLE, format = params.format.extension());
+    tokio::spawn(write_chunks(first, rows, encoder, sender).instrument(span));
+
+    let filename = match params.as_of {
+        Some(as_of) => format!("{}-{}.{}", R::TABLE, as_of, params.format.extension()),
+        None => format!("{}.{}", R::TABLE, params.format.extension()),
+    };
+    Response::builder()
+        .header(CONTENT_TYPE, params.format.content_type())
+        .header(CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename))
+        .header(CACHE_CONTROL, "no-store")
+        .body(Body::from_stream(ReceiverStream::new(receiver)))
+        .map_err(ApiError::internal)
+}
+
+// Encodes rows as they arrive and forwards the output a chunk at a time. Stops early when the
+// client goes away, which also drops the row receiver and ends the query.
+async fn write_chunks<R: Resource>(
+    first: Option<R>,
+    rows: mpsc::Receiver<Result<R, ApiError>>,
+    mut encoder: Encoder,
+    sender: mpsc::Sender<Result<axum::body::Bytes, io::Error>>,
+) {
+    let mut rows = stream::iter(first.map(Ok)).chain(ReceiverStream::new(rows));
+    let mut count = 0usize;
+    while let Some(row) = rows.next().await {
+        let encoded = row
+            .map_err(|e| io::Error::other(e.message()))
+            .and_then(|row| encoder.push(&row).map_err(io::Error::other));
+        if let Err(e) = encoded {
+            error!(error = %e, rows = count, "Export failed");
+            let _ = sender.send(Err(e)).await;
+            return;
+        }
+        count += 1;
+        if count.is_multiple_of(CHUNK_ROWS) {
+            let chunk = encoder.take().map_err(io::Error::other);
+            let failed = chunk.is_err();
+            if sender.send(chunk).await.is_err() || failed {
+                return;
+            }
+        }
+    }
+
+    let last = encoder.finish().map_err(io::Error::other);
+    if let Err(e) = &last {
+        error!(error = %e, rows = count, "Export failed");
+    } else {
+        info!(rows = count, "Export finished");
+    }
+    let _ = sender.send(last).await;
+}
@@ -0,0 +1,3 @@
+pub mod encode;
+pub mod handlers;
+pub mod routes;
@@ -0,0 +1,11 @@
+// export/routes.rs
+use axum::{routing::get, Router};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::export::handlers::export_handler;
+
+pub fn create_export_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/{resource}", get(export_handler))
+        .with_state(app_state)
+}
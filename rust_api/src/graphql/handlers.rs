@@ -0,0 +1,30 @@
+use async_graphql::Request;
+use axum::extract::{rejection::JsonRejection, Extension, Json, State};
+use serde_json::to_value;
+use std::sync::Arc;
+
+use employee_core::auth::Claims;
+use employee_core::db::AppState;
+use employee_core::error::ApiError;
+use employee_core::sodium::sodium_crypto::{encrypt_json, get_key};
+
+use crate::graphql::{loaders, schema::EmployeeSchema};
+
+// POST /graphql with {"query": ..., "variables": ...}. The token is checked like on every other
+// route, and the GraphQL response (data and errors) is sealed like the REST bodies.
+pub async fn graphql_handler(
+    claims: Claims,
+    State(state): State<Arc<AppState>>,
+    Extension(schema): Extension<EmployeeSchema>,
+    payload: Result<Json<Request>, JsonRejection>,
+) -> Result<Json<String>, ApiError> {
+    let Json(request) = payload?;
+    let request = loaders::register(request, &state.db)
+        .data(claims)
+        .data(state.clone());
+
+    let response = schema.execute(request).await;
+    let json_data = to_value(&response).map_err(ApiError::internal)?;
+    let encrypted_data = encrypt_json(&json_data, &get_key()).map_err(ApiError::internal)?;
+    Ok(Json(encrypted_data))
+}
@@ -0,0 +1,4 @@
+pub mod handlers;
+pub mod loaders;
+pub mod routes;
+pub mod schema;
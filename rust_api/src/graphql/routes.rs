@@ -0,0 +1,15 @@
+// graphql/routes.rs
+use axum::{
+    routing::post,
+    Extension, Router,
+};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::graphql::{handlers::graphql_handler, schema::build_schema};
+
+pub fn create_graphql_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(graphql_handler))
+        .layer(Extension(build_schema()))
+        .with_state(app_state)
+}
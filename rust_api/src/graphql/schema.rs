@@ -0,0 +1,380 @@
+// Read-only GraphQL over the employee schema. Writes stay with the action API, where they are
+// validated, versioned and audited. Soft-deleted rows are never returned.
+use async_graphql::{
+    dataloader::{DataLoader, Loader},
+    Context, EmptyMutation, EmptySubscription, Error, ErrorExtensions, Object, Result, Schema,
+};
+use chrono::NaiveDate;
+use sqlx::query_as;
+use std::{hash::Hash, sync::Arc};
+
+use employee_core::db::{open_end_date, AppState};
+use employee_core::departments::models::Department;
+use employee_core::dept_emp::models::DeptEmp;
+use employee_core::dept_manager::models::DeptManager;
+use employee_core::employees::models::Employee;
+use employee_core::error::ApiError;
+use employee_core::salaries::models::Salary;
+use employee_core::titles::models::Title;
+
+use crate::graphql::loaders::RowsBy;
+
+pub type EmployeeSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+// Deep enough for employee -> department -> manager -> salaries and back, small enough
+// that a single query cannot walk the whole database.
+const MAX_DEPTH: usize = 10;
+const MAX_COMPLEXITY: usize = 500;
+const MAX_PAGE: i32 = 100;
+
+pub fn build_schema() -> EmployeeSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+// Same code and message as the REST error body, carried in the GraphQL error's extensions.
+fn api_error(error: ApiError) -> Error {
+    let code = error.code();
+    Error::new(error.message()).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+// A loader error is shared by every resolver waiting on the batch; only the last owner can
+// classify it, the others report it as internal.
+fn db_error(error: Arc<sqlx::Error>) -> Error {
+    api_error(Arc::try_unwrap(error).map(ApiError::from).unwrap_or_else(ApiError::internal))
+}
+
+fn state<'a>(ctx: &Context<'a>) -> &'a Arc<AppState> {
+    ctx.data_unchecked::<Arc<AppState>>()
+}
+
+// Every live row the loader holds for the key, oldest first.
+async fn load_all<R, K>(ctx: &Context<'_>, key: K) -> Result<Vec<Arc<R>>>
+where
+    RowsBy<R, K>: Loader<K, Value = Vec<Arc<R>>, Error = Arc<sqlx::Error>>,
+    K: Send + Sync + Hash + Eq + Clone + 'static,
+{
+    let loader = ctx.data_unchecked::<DataLoader<RowsBy<R, K>>>();
+    Ok(loader.load_one(key).await.map_err(db_error)?.unwrap_or_default())
+}
+
+async fn load_one<R, K>(ctx: &Context<'_>, key: K) -> Result<Option<Arc<R>>>
+where
+    RowsBy<R, K>: Loader<K, Value = Vec<Arc<R>>, Error = Arc<sqlx::Error>>,
+    K: Send + Sync + Hash + Eq + Clone + 'static,
+{
+    Ok(load_all::<R, K>(ctx, key).await?.into_iter().next())
+}
+
+// History rows that are still open carry the 9999-01-01 end date.
+fn is_current(to_date: NaiveDate) -> bool {
+    to_date == open_end_date()
+}
+
+fn page(limit: i32) -> i32 {
+    limit.clamp(1, MAX_PAGE)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn employee(&self, ctx: &Context<'_>, emp_no: i32) -> Result<Option<EmployeeNode>> {
+        Ok(load_one::<Employee, i32>(ctx, emp_no).await?.map(EmployeeNode))
+    }
+
+    // Keyset pagination by emp_no: pass the last emp_no of a page as after_emp_no for the next.
+    async fn employees(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0)] after_emp_no: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<EmployeeNode>> {
+        let query_str = "SELECT * FROM employees WHERE emp_no > ? AND deleted_at IS NULL ORDER BY emp_no LIMIT ?";
+        let rows = query_as::<_, Employee>(query_str)
+            .bind(after_emp_no)
+            .bind(page(limit))
+            .fetch_all(&state(ctx).db)
+            .await
+            .map_err(|e| api_error(e.into()))?;
+        Ok(rows.into_iter().map(|row| EmployeeNode(Arc::new(row))).collect())
+    }
+
+    async fn department(&self, ctx: &Context<'_>, dept_no: String) -> Result<Option<DepartmentNode>> {
+        Ok(load_one::<Department, String>(ctx, dept_no).await?.map(DepartmentNode))
+    }
+
+    async fn departments(&self, ctx: &Context<'_>) -> Result<Vec<DepartmentNode>> {
+        let query_str = "SELECT * FROM departments WHERE deleted_at IS NULL ORDER BY dept_no";
+        let rows = query_as::<_, Department>(query_str)
+            .fetch_all(&state(ctx).db)
+            .await
+            .map_err(|e| api_error(e.into()))?;
+        Ok(rows.into_iter().map(|row| DepartmentNode(Arc::new(row))).collect())
+    }
+}
+
+pub struct EmployeeNode(Arc<Employee>);
+
+#[Object(name = "Employee")]
+impl EmployeeNode {
+    async fn emp_no(&self) -> i32 {
+        self.0.emp_no
+    }
+
+    async fn birth_date(&self) -> NaiveDate {
+        self.0.birth_date
+    }
+
+    async fn first_name(&self) -> &str {
+        &self.0.first_name
+    }
+
+    async fn last_name(&self) -> &str {
+        &self.0.last_name
+    }
+
+    async fn gender(&self) -> &str {
+        &self.0.gender
+    }
+
+    async fn hire_date(&self) -> NaiveDate {
+        self.0.hire_date
+    }
+
+    async fn row_version(&self) -> i32 {
+        self.0.row_version
+    }
+
+    // Every department stint, oldest first.
+    async fn departments(&self, ctx: &Context<'_>) -> Result<Vec<DeptEmpNode>> {
+        Ok(load_all::<DeptEmp, i32>(ctx, self.0.emp_no).await?.into_iter().map(DeptEmpNode).collect())
+    }
+
+    async fn current_department(&self, ctx: &Context<'_>) -> Result<Option<DepartmentNode>> {
+        match self.current_dept_no(ctx).await? {
+            Some(dept_no) => Ok(load_one::<Department, String>(ctx, dept_no).await?.map(DepartmentNode)),
+            None => Ok(None),
+        }
+    }
+
+    // The current manager of the employee's current department.
+    async fn manager(&self, ctx: &Context<'_>) -> Result<Option<EmployeeNode>> {
+        let Some(dept_no) = self.current_dept_no(ctx).await? else {
+            return Ok(None);
+        };
+        current_manager(ctx, dept_no).await
+    }
+
+    async fn titles(&self, ctx: &Context<'_>) -> Result<Vec<TitleNode>> {
+        Ok(load_all::<Title, i32>(ctx, self.0.emp_no).await?.into_iter().map(TitleNode).collect())
+    }
+
+    async fn current_title(&self, ctx: &Context<'_>) -> Result<Option<TitleNode>> {
+        let titles = load_all::<Title, i32>(ctx, self.0.emp_no).await?;
+        Ok(titles.into_iter().rfind(|title| title.to_date.is_none_or(is_current)).map(TitleNode))
+    }
+
+    async fn salaries(&self, ctx: &Context<'_>) -> Result<Vec<SalaryNode>> {
+        Ok(load_all::<Salary, i32>(ctx, self.0.emp_no).await?.into_iter().map(SalaryNode).collect())
+    }
+
+    async fn current_salary(&self, ctx: &Context<'_>) -> Result<Option<SalaryNode>> {
+        let salaries = load_all::<Salary, i32>(ctx, self.0.emp_no).await?;
+        Ok(salaries.into_iter().rfind(|salary| is_current(salary.to_date)).map(SalaryNode))
+    }
+
+    // Departments this employee has managed, oldest first.
+    async fn managed_departments(&self, ctx: &Context<'_>) -> Result<Vec<DeptManagerNode>> {
+        Ok(load_all::<DeptManager, i32>(ctx, self.0.emp_no).await?.into_iter().map(DeptManagerNode).collect())
+    }
+}
+
+impl EmployeeNode {
+    async fn current_dept_no(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let stints = load_all::<DeptEmp, i32>(ctx, self.0.emp_no).await?;
+        Ok(stints.iter().rfind(|stint| is_current(stint.to_date)).map(|stint| stint.dept_no.clone()))
+    }
+}
+
+async fn current_manager(ctx: &Context<'_>, dept_no: String) -> Result<Option<EmployeeNode>> {
+    let managers = load_all::<DeptManager, String>(ctx, dept_no).await?;
+    match managers.iter().rfind(|manager| is_current(manager.to_date)) {
+        Some(manager) => Ok(load_one::<Employee, i32>(ctx, manager.emp_no).await?.map(EmployeeNode)),
+        None => Ok(None),
+    }
+}
+
+pub struct DepartmentNode(Arc<Department>);
+
+#[Object(name = "Department")]
+impl DepartmentNode {
+    async fn dept_no(&self) -> &str {
+        &self.0.dept_no
+    }
+
+    async fn dept_name(&self) -> &str {
+        &self.0.dept_name
+    }
+
+    async fn row_version(&self) -> i32 {
+        self.0.row_version
+    }
+
+    // Every manager the department has had, oldest first.
+    async fn managers(&self, ctx: &Context<'_>) -> Result<Vec<DeptManagerNode>> {
+        Ok(load_all::<DeptManager, String>(ctx, self.0.dept_no.clone()).await?.into_iter().map(DeptManagerNode).collect())
+    }
+
+    async fn current_manager(&self, ctx: &Context<'_>) -> Result<Option<EmployeeNode>> {
+        current_manager(ctx, self.0.dept_no.clone()).await
+    }
+
+    // Current members, paged by emp_no like Query.employees. Departments are large, so this
+    // runs one query per department instead of going through a loader.
+    async fn employees(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0)] after_emp_no: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<EmployeeNode>> {
+        let query_str = "SELECT e.* FROM employees e JOIN dept_emp de ON de.emp_no = e.emp_no \
+            WHERE de.dept_no = ? AND de.to_date = ? AND de.deleted_at IS NULL AND e.deleted_at IS NULL AND e.emp_no > ? \
+            ORDER BY e.emp_no LIMIT ?";
+        let rows = query_as::<_, Employee>(query_str)
+            .bind(&self.0.dept_no)
+            .bind(open_end_date())
+            .bind(after_emp_no)
+            .bind(page(limit))
+            .fetch_all(&state(ctx).db)
+            .await
+            .map_err(|e| api_error(e.into()))?;
+        Ok(rows.into_iter().map(|row| EmployeeNode(Arc::new(row))).collect())
+    }
+}
+
+pub struct DeptEmpNode(Arc<DeptEmp>);
+
+#[Object(name = "DeptEmp")]
+impl DeptEmpNode {
+    async fn emp_no(&self) -> i32 {
+        self.0.emp_no
+    }
+
+    async fn dept_no(&self) -> &str {
+        &self.0.dept_no
+    }
+
+    async fn from_date(&self) -> NaiveDate {
+        self.0.from_date
+    }
+
+    async fn to_date(&self) -> NaiveDate {
+        self.0.to_date
+    }
+
+    async fn row_version(&self) -> i32 {
+        self.0.row_version
+    }
+
+    async fn employee(&self, ctx: &Context<'_>) -> Result<Option<EmployeeNode>> {
+        Ok(load_one::<Employee, i32>(ctx, self.0.emp_no).await?.map(EmployeeNode))
+    }
+
+    async fn department(&self, ctx: &Context<'_>) -> Result<Option<DepartmentNode>> {
+        Ok(load_one::<Department, String>(ctx, self.0.dept_no.clone()).await?.map(DepartmentNode))
+    }
+}
+
+pub struct DeptManagerNode(Arc<DeptManager>);
+
+#[Object(name = "DeptManager")]
+impl DeptManagerNode {
+    async fn emp_no(&self) -> i32 {
+        self.0.emp_no
+    }
+
+    async fn dept_no(&self) -> &str {
+        &self.0.dept_no
+    }
+
+    async fn from_date(&self) -> NaiveDate {
+        self.0.from_date
+    }
+
+    async fn to_date(&self) -> NaiveDate {
+        self.0.to_date
+    }
+
+    async fn row_version(&self) -> i32 {
+        self.0.row_version
+    }
+
+    async fn employee(&self, ctx: &Context<'_>) -> Result<Option<EmployeeNode>> {
+        Ok(load_one::<Employee, i32>(ctx, self.0.emp_no).await?.map(EmployeeNode))
+    }
+
+    async fn department(&self, ctx: &Context<'_>) -> Result<Option<DepartmentNode>> {
+        Ok(load_one::<Department, String>(ctx, self.0.dept_no.clone()).await?.map(DepartmentNode))
+    }
+}
+
+pub struct TitleNode(Arc<Title>);
+
+#[Object(name = "Title")]
+impl TitleNode {
+    async fn emp_no(&self) -> i32 {
+        self.0.emp_no
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn from_date(&self) -> NaiveDate {
+        self.0.from_date
+    }
+
+    async fn to_date(&self) -> Option<NaiveDate> {
+        self.0.to_date
+    }
+
+    async fn row_version(&self) -> i32 {
+        self.0.row_version
+    }
+
+    async fn employee(&self, ctx: &Context<'_>) -> Result<Option<EmployeeNode>> {
+        Ok(load_one::<Employee, i32>(ctx, self.0.emp_no).await?.map(EmployeeNode))
+    }
+}
+
+pub struct SalaryNode(Arc<Salary>);
+
+#[Object(name = "Salary")]
+impl SalaryNode {
+    async fn emp_no(&self) -> i32 {
+        self.0.emp_no
+    }
+
+    async fn salary(&self) -> i32 {
+        self.0.salary
+    }
+
+    async fn from_date(&self) -> NaiveDate {
+        self.0.from_date
+    }
+
+    async fn to_date(&self) -> NaiveDate {
+        self.0.to_date
+    }
+
+    async fn row_version(&self) -> i32 {
+        self.0.row_version
+    }
+
+    async fn employee(&self, ctx: &Context<'_>) -> Result<Option<EmployeeNode>> {
+        Ok(load_one::<Employee, i32>(ctx, self.0.emp_no).await?.map(EmployeeNode))
+    }
+}
@@ -0,0 +1,66 @@
+// Bearer JWT checks for the gRPC server: the same tokens, secret and leeway as the JSON API.
+use axum::http::HeaderMap;
+use jsonwebtoken::errors::ErrorKind;
+use std::sync::Arc;
+use tonic::{Request, Status};
+use uuid::Uuid;
+
+use employee_core::audit::AuditContext;
+use employee_core::auth::{validate_jwt, Claims};
+use employee_core::config::JwtConfig;
+use employee_core::db::AppState;
+use employee_core::metrics;
+use employee_core::repository::CrudContext;
+use employee_core::request_id::{RequestId, REQUEST_ID_HEADER};
+
+// Interceptor for every service: rejects calls without a valid token and leaves the Claims
+// in the request extensions for the handlers.
+pub fn authenticate(config: JwtConfig) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone {
+    move |mut request: Request<()>| {
+        let token = request.metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| {
+                metrics::auth_rejected(401, "missing_token");
+                Status::unauthenticated("Missing bearer token")
+            })?;
+
+        let claims = validate_jwt(token, &config).map_err(|e| match e.kind() {
+            ErrorKind::ExpiredSignature => {
+                metrics::auth_rejected(401, "expired");
+                Status::unauthenticated("Token expired")
+            }
+            _ => {
+                metrics::auth_rejected(401, "invalid");
+                Status::unauthenticated("Invalid token")
+            }
+        })?;
+
+        request.extensions_mut().insert(claims);
+        Ok(request)
+    }
+}
+
+// What the repository needs for one call. The x-request-id metadata is reused when sent so a
+// caller can find its writes in the audit log.
+pub fn context<T>(state: &Arc<AppState>, request: &Request<T>) -> Result<CrudContext, Status> {
+    let claims = request.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("Missing bearer token"))?;
+    let request_id = request.metadata()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= 64)
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    Ok(CrudContext {
+        state: state.clone(),
+        audit: AuditContext::new(&claims, &RequestId(request_id)),
+        claims,
+        // Versions travel in the request messages; there is no If-Match here.
+        headers: HeaderMap::new(),
+    })
+}
@@ -0,0 +1,385 @@
+// Conversions between the protobuf messages and the core models, and from ApiError to a
+// gRPC status. Incoming dates are validated here; everything else is left to the same
+// Validate impls the JSON API uses.
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::to_vec;
+use tonic::{Code, Status};
+
+use employee_core::departments::models::{Department, DepartmentKey};
+use employee_core::dept_emp::models::{DeptEmp, DeptEmpKey};
+use employee_core::dept_manager::models::{DeptManager, DeptManagerKey};
+use employee_core::employees::models::{
+    Employee, EmployeeKey, RehireEmployee, RehireResult, TerminateEmployee, TerminationResult,
+};
+use employee_core::error::{ApiError, ErrorBody};
+use employee_core::salaries::models::{DepartmentAdjustmentResult, Salary, SalaryAdjustment, SalaryAdjustmentResult, SalaryKey};
+use employee_core::titles::models::{Title, TitleKey};
+
+use crate::grpc::proto;
+
+// The same code and message as the JSON error body; the body itself, as JSON, goes in the
+// status details so field-level validation errors are not lost.
+pub fn status(error: ApiError) -> Status {
+    let code = match &error {
+        ApiError::BadRequest(_) | ApiError::Validation(_) => Code::InvalidArgument,
+        ApiError::Unauthorized(_) => Code::Unauthenticated,
+        ApiError::Forbidden(_) => Code::PermissionDenied,
+        ApiError::NotFound(_) => Code::NotFound,
+        ApiError::VersionConflict { .. } => Code::Aborted,
+        ApiError::Conflict(_) | ApiError::PreconditionRequired(_) | ApiError::Unprocessable(_) => Code::FailedPrecondition,
+        ApiError::TooManyRequests(_) => Code::ResourceExhausted,
+        ApiError::Unavailable(_) => Code::Unavailable,
+        ApiError::Internal(_) => Code::Internal,
+    };
+    let body = ErrorBody {
+        code: error.code().to_string(),
+        message: error.message(),
+        details: error.details(),
+        request_id: None,
+    };
+    Status::with_details(code, body.message.clone(), to_vec(&body).unwrap_or_default().into())
+}
+
+fn date(field: &str, value: &str) -> Result<NaiveDate, Status> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| Status::invalid_argument(format!("{}: expected a YYYY-MM-DD date, got {:?}", field, value)))
+}
+
+fn optional_date(field: &str, value: Option<String>) -> Result<Option<NaiveDate>, Status> {
+    value.map(|value| date(field, &value)).transpose()
+}
+
+pub fn required<T>(field: &str, value: Option<T>) -> Result<T, Status> {
+    value.ok_or_else(|| Status::invalid_argument(format!("{} is required", field)))
+}
+
+fn iso(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn timestamp(value: Option<NaiveDateTime>) -> Option<String> {
+    value.map(|value| value.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+// ---- rows ----
+
+impl TryFrom<proto::Employee> for Employee {
+    type Error = Status;
+
+    fn try_from(message: proto::Employee) -> Result<Self, Status> {
+        Ok(Employee {
+            emp_no: message.emp_no,
+            birth_date: date("birth_date", &message.birth_date)?,
+            first_name: message.first_name,
+            last_name: message.last_name,
+            gender: message.gender,
+            hire_date: date("hire_date", &message.hire_date)?,
+            row_version: message.row_version,
+            deleted_at: None,
+        })
+    }
+}
+
+impl From<Employee> for proto::Employee {
+    fn from(row: Employee) -> Self {
+        proto::Employee {
+            emp_no: row.emp_no,
+            birth_date: iso(row.birth_date),
+            first_name: row.first_name,
+            last_name: row.last_name,
+            gender: row.gender,
+            hire_date: iso(row.hire_date),
+            row_version: row.row_version,
+            deleted_at: timestamp(row.deleted_at),
+        }
+    }
+}
+
+impl TryFrom<proto::Department> for Department {
+    type Error = Status;
+
+    fn try_from(message: proto::Department) -> Result<Self, Status> {
+        Ok(Department {
+            dept_no: message.dept_no,
+            dept_name: message.dept_name,
+            row_version: message.row_version,
+            deleted_at: None,
+        })
+    }
+}
+
+impl From<Department> for proto::Department {
+    fn from(row: Department) -> Self {
+        proto::Department {
+            dept_no: row.dept_no,
+            dept_name: row.dept_name,
+            row_version: row.row_version,
+            deleted_at: timestamp(row.deleted_at),
+        }
+    }
+}
+
+impl TryFrom<proto::DeptEmp> for DeptEmp {
+    type Error = Status;
+
+    fn try_from(message: proto::DeptEmp) -> Result<Self, Status> {
+        Ok(DeptEmp {
+            emp_no: message.emp_no,
+            dept_no: message.dept_no,
+            from_date: date("from_date", &message.from_date)?,
+            to_date: date("to_date", &message.to_date)?,
+            row_version: message.row_version,
+            deleted_at: None,
+        })
+    }
+}
+
+impl From<DeptEmp> for proto::DeptEmp {
+    fn from(row: DeptEmp) -> Self {
+        proto::DeptEmp {
+            emp_no: row.emp_no,
+            dept_no: row.dept_no,
+            from_date: iso(row.from_date),
+            to_date: iso(row.to_date),
+            row_version: row.row_version,
+            deleted_at: timestamp(row.deleted_at),
+        }
+    }
+}
+
+impl TryFrom<proto::DeptManager> for DeptManager {
+    type Error = Status;
+
+    fn try_from(message: proto::DeptManager) -> Result<Self, Status> {
+        Ok(DeptManager {
+            emp_no: message.emp_no,
+            dept_no: message.dept_no,
+            from_date: date("from_date", &message.from_date)?,
+            to_date: date("to_date", &message.to_date)?,
+            row_version: message.row_version,
+            deleted_at: None,
+        })
+    }
+}
+
+impl From<DeptManager> for proto::DeptManager {
+    fn from(row: DeptManager) -> Self {
+        proto::DeptManager {
+            emp_no: row.emp_no,
+            dept_no: row.dept_no,
+            from_date: iso(row.from_date),
+            to_date: iso(row.to_date),
+            row_version: row.row_version,
+            deleted_at: timestamp(row.deleted_at),
+        }
+    }
+}
+
+impl TryFrom<proto::Title> for Title {
+    type Error = Status;
+
+    fn try_from(message: proto::Title) -> Result<Self, Status> {
+        Ok(Title {
+            emp_no: message.emp_no,
+            title: message.title,
+            from_date: date("from_date", &message.from_date)?,
+            to_date: optional_date("to_date", message.to_date)?,
+            row_version: message.row_version,
+            deleted_at: None,
+        })
+    }
+}
+
+impl From<Title> for proto::Title {
+    fn from(row: Title) -> Self {
+        proto::Title {
+            emp_no: row.emp_no,
+            title: row.title,
+            from_date: iso(row.from_date),
+            to_date: row.to_date.map(iso),
+            row_version: row.row_version,
+            deleted_at: timestamp(row.deleted_at),
+        }
+    }
+}
+
+impl TryFrom<proto::Salary> for Salary {
+    type Error = Status;
+
+    fn try_from(message: proto::Salary) -> Result<Self, Status> {
+        Ok(Salary {
+            emp_no: message.emp_no,
+            salary: message.salary,
+            from_date: date("from_date", &message.from_date)?,
+            to_date: date("to_date", &message.to_date)?,
+            row_version: message.row_version,
+            deleted_at: None,
+        })
+    }
+}
+
+impl From<Salary> for proto::Salary {
+    fn from(row: Salary) -> Self {
+        proto::Salary {
+            emp_no: row.emp_no,
+            salary: row.salary,
+            from_date: iso(row.from_date),
+            to_date: iso(row.to_date),
+            row_version: row.row_version,
+            deleted_at: timestamp(row.deleted_at),
+        }
+    }
+}
+
+// ---- keys ----
+
+impl TryFrom<proto::EmployeeKey> for EmployeeKey {
+    type Error = Status;
+
+    fn try_from(message: proto::EmployeeKey) -> Result<Self, Status> {
+        Ok(EmployeeKey { emp_no: message.emp_no })
+    }
+}
+
+impl TryFrom<proto::DepartmentKey> for DepartmentKey {
+    type Error = Status;
+
+    fn try_from(message: proto::DepartmentKey) -> Result<Self, Status> {
+        Ok(DepartmentKey { dept_no: message.dept_no })
+    }
+}
+
+impl TryFrom<proto::DeptEmpKey> for DeptEmpKey {
+    type Error = Status;
+
+    fn try_from(message: proto::DeptEmpKey) -> Result<Self, Status> {
+        Ok(DeptEmpKey {
+            emp_no: message.emp_no,
+            from_date: date("from_date", &message.from_date)?,
+            dept_no: message.dept_no,
+        })
+    }
+}
+
+impl TryFrom<proto::DeptManagerKey> for DeptManagerKey {
+    type Error = Status;
+
+    fn try_from(message: proto::DeptManagerKey) -> Result<Self, Status> {
+        Ok(DeptManagerKey { emp_no: message.emp_no, dept_no: message.dept_no })
+    }
+}
+
+impl TryFrom<proto::TitleKey> for TitleKey {
+    type Error = Status;
+
+    fn try_from(message: proto::TitleKey) -> Result<Self, Status> {
+        Ok(TitleKey {
+            emp_no: message.emp_no,
+            title: message.title,
+            from_date: date("from_date", &message.from_date)?,
+        })
+    }
+}
+
+impl TryFrom<proto::SalaryKey> for SalaryKey {
+    type Error = Status;
+
+    fn try_from(message: proto::SalaryKey) -> Result<Self, Status> {
+        Ok(SalaryKey {
+            emp_no: message.emp_no,
+            from_date: date("from_date", &message.from_date)?,
+        })
+    }
+}
+
+// ---- employee and salary actions ----
+
+impl TryFrom<proto::TerminateEmployeeRequest> for TerminateEmployee {
+    type Error = Status;
+
+    fn try_from(message: proto::TerminateEmployeeRequest) -> Result<Self, Status> {
+        Ok(TerminateEmployee {
+            termination_date: date("termination_date", &message.termination_date)?,
+            reason: message.reason,
+        })
+    }
+}
+
+impl From<TerminationResult> for proto::TerminationResult {
+    fn from(result: TerminationResult) -> Self {
+        let termination = result.termination;
+        proto::TerminationResult {
+            termination: Some(proto::Termination {
+                emp_no: termination.emp_no,
+                termination_date: iso(termination.termination_date),
+                reason: termination.reason,
+                rehire_date: termination.rehire_date.map(iso),
+            }),
+            closed_dept_emp: result.closed_dept_emp,
+            closed_dept_manager: result.closed_dept_manager,
+            closed_titles: result.closed_titles,
+            closed_salaries: result.closed_salaries,
+        }
+    }
+}
+
+impl TryFrom<proto::RehireEmployeeRequest> for RehireEmployee {
+    type Error = Status;
+
+    fn try_from(message: proto::RehireEmployeeRequest) -> Result<Self, Status> {
+        Ok(RehireEmployee {
+            rehire_date: date("rehire_date", &message.rehire_date)?,
+            dept_no: message.dept_no,
+            title: message.title,
+            salary: message.salary,
+        })
+    }
+}
+
+impl From<RehireResult> for proto::RehireResult {
+    fn from(result: RehireResult) -> Self {
+        proto::RehireResult {
+            emp_no: result.emp_no,
+            rehire_date: iso(result.rehire_date),
+            dept_emp: Some(result.dept_emp.into()),
+            title: Some(result.title.into()),
+            salary: Some(result.salary.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::SalaryAdjustment> for SalaryAdjustment {
+    type Error = Status;
+
+    fn try_from(message: proto::SalaryAdjustment) -> Result<Self, Status> {
+        Ok(SalaryAdjustment {
+            amount: message.amount,
+            percent: message.percent,
+            effective_date: date("adjustment.effective_date", &message.effective_date)?,
+        })
+    }
+}
+
+impl From<SalaryAdjustmentResult> for proto::SalaryAdjustmentResult {
+    fn from(result: SalaryAdjustmentResult) -> Self {
+        proto::SalaryAdjustmentResult {
+            emp_no: result.emp_no,
+            before: result.before,
+            after: result.after,
+            effective_date: iso(result.effective_date),
+        }
+    }
+}
+
+impl From<DepartmentAdjustmentResult> for proto::DepartmentAdjustmentResult {
+    fn from(result: DepartmentAdjustmentResult) -> Self {
+        proto::DepartmentAdjustmentResult {
+            dept_no: result.dept_no,
+            effective_date: iso(result.effective_date),
+            dry_run: result.dry_run,
+            total_before: result.total_before,
+            total_after: result.total_after,
+            adjustments: result.adjustments.into_iter().map(Into::into).collect(),
+        }
+    }
+}
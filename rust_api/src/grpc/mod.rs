@@ -0,0 +1,52 @@
+pub mod auth;
+pub mod convert;
+pub mod services;
+
+use std::sync::Arc;
+use tonic::transport::Server;
+use tracing::{error, info};
+
+use employee_core::db::AppState;
+
+use crate::grpc::services::GrpcService;
+
+pub mod proto {
+    tonic::include_proto!("employees.v1");
+}
+
+use proto::{
+    department_service_server::DepartmentServiceServer,
+    dept_emp_service_server::DeptEmpServiceServer,
+    dept_manager_service_server::DeptManagerServiceServer,
+    employee_service_server::EmployeeServiceServer,
+    salary_service_server::SalaryServiceServer,
+    title_service_server::TitleServiceServer,
+};
+
+// Runs the gRPC server on grpc.port until it fails; the JSON API keeps running either way.
+pub async fn serve(state: Arc<AppState>) {
+    let config = &state.config;
+    let addr = match config.grpc_addr().parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            error!(addr = %config.grpc_addr(), error = %e, "gRPC address is not usable");
+            return;
+        }
+    };
+    let service = GrpcService { state: state.clone() };
+    let interceptor = auth::authenticate(config.jwt.clone());
+
+    info!(%addr, "gRPC server started");
+    let result = Server::builder()
+        .add_service(EmployeeServiceServer::with_interceptor(service.clone(), interceptor.clone()))
+        .add_service(DepartmentServiceServer::with_interceptor(service.clone(), interceptor.clone()))
+        .add_service(DeptEmpServiceServer::with_interceptor(service.clone(), interceptor.clone()))
+        .add_service(DeptManagerServiceServer::with_interceptor(service.clone(), interceptor.clone()))
+        .add_service(TitleServiceServer::with_interceptor(service.clone(), interceptor.clone()))
+        .add_service(SalaryServiceServer::with_interceptor(service, interceptor))
+        .serve(addr)
+        .await;
+    if let Err(e) = result {
+        error!(%addr, error = %e, "gRPC server stopped");
+    }
+}
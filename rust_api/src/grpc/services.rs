@@ -0,0 +1,371 @@
+// One struct serves all six services. The CRUD calls go through the generic helpers below,
+// which run the same repository functions as the JSON API.
+use std::{pin::Pin, sync::Arc};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use employee_core::db::AppState;
+use employee_core::departments::models::Department;
+use employee_core::dept_emp::models::DeptEmp;
+use employee_core::dept_manager::models::DeptManager;
+use employee_core::employees::models::Employee;
+use employee_core::repository::{crud, stream_all, CrudContext, Resource};
+use employee_core::salaries::models::Salary;
+use employee_core::titles::models::Title;
+
+use crate::employees::handlers::{rehire_employee, terminate_employee};
+use crate::grpc::auth::context;
+use crate::grpc::convert::{required, status};
+use crate::grpc::proto::{
+    self,
+    department_service_server::DepartmentService,
+    dept_emp_service_server::DeptEmpService,
+    dept_manager_service_server::DeptManagerService,
+    employee_service_server::EmployeeService,
+    salary_service_server::SalaryService,
+    title_service_server::TitleService,
+};
+use crate::salaries::handlers::{adjust_department_salaries, adjust_salary};
+
+pub type RowStream<M> = Pin<Box<dyn Stream<Item = Result<M, Status>> + Send>>;
+
+#[derive(Clone)]
+pub struct GrpcService {
+    pub state: Arc<AppState>,
+}
+
+impl GrpcService {
+    fn context<T>(&self, request: &Request<T>) -> Result<CrudContext, Status> {
+        context(&self.state, request)
+    }
+}
+
+async fn create_row<R, M>(ctx: CrudContext, message: M) -> Result<Response<M>, Status>
+where
+    R: Resource + TryFrom<M, Error = Status>,
+    M: From<R>,
+{
+    let row = R::try_from(message)?;
+    let row = crud::create(&ctx, row).await.map_err(status)?;
+    Ok(Response::new(row.into()))
+}
+
+async fn get_row<R, M, K>(ctx: CrudContext, key: Option<K>, include_deleted: bool) -> Result<Response<M>, Status>
+where
+    R: Resource,
+    R::Key: TryFrom<K, Error = Status>,
+    M: From<R>,
+{
+    let key = R::Key::try_from(required("key", key)?)?;
+    let row = crud::read::<R>(&ctx, key, include_deleted).await.map_err(status)?;
+    Ok(Response::new(row.into()))
+}
+
+// Rows are sent as the database cursor yields them, so a full table never sits in memory.
+// Unbounded like /export, so admin only like /export.
+fn list_rows<R, M>(ctx: CrudContext, request: proto::ListRequest) -> Result<Response<RowStream<M>>, Status>
+where
+    R: Resource,
+    M: From<R> + Send + 'static,
+{
+    ctx.claims.require_admin().map_err(status)?;
+    let rows = stream_all::<R>(&ctx, request.include_deleted).map_err(status)?;
+    let stream = ReceiverStream::new(rows).map(|row| row.map(M::from).map_err(status));
+    Ok(Response::new(Box::pin(stream)))
+}
+
+async fn update_row<R, M, K>(ctx: CrudContext, key: Option<K>, version: Option<i32>, row: Option<M>) -> Result<Response<M>, Status>
+where
+    R: Resource + TryFrom<M, Error = Status>,
+    R::Key: TryFrom<K, Error = Status>,
+    M: From<R>,
+{
+    let key = R::Key::try_from(required("key", key)?)?;
+    let row = R::try_from(required("row", row)?)?;
+    let row = crud::update(&ctx, key, version, row).await.map_err(status)?;
+    Ok(Response::new(row.into()))
+}
+
+async fn delete_row<R, K>(ctx: CrudContext, key: K) -> Result<Response<proto::ActionReply>, Status>
+where
+    R: Resource,
+    R::Key: TryFrom<K, Error = Status>,
+{
+    let message = crud::delete::<R>(&ctx, R::Key::try_from(key)?).await.map_err(status)?;
+    Ok(Response::new(proto::ActionReply { message }))
+}
+
+async fn restore_row<R, K>(ctx: CrudContext, key: K) -> Result<Response<proto::ActionReply>, Status>
+where
+    R: Resource,
+    R::Key: TryFrom<K, Error = Status>,
+{
+    let message = crud::restore::<R>(&ctx, R::Key::try_from(key)?).await.map_err(status)?;
+    Ok(Response::new(proto::ActionReply { message }))
+}
+
+async fn purge_row<R, K>(ctx: CrudContext, key: K) -> Result<Response<proto::ActionReply>, Status>
+where
+    R: Resource,
+    R::Key: TryFrom<K, Error = Status>,
+{
+    let message = crud::purge::<R>(&ctx, R::Key::try_from(key)?).await.map_err(status)?;
+    Ok(Response::new(proto::ActionReply { message }))
+}
+
+#[tonic::async_trait]
+impl EmployeeService for GrpcService {
+    type ListStream = RowStream<proto::Employee>;
+
+    async fn create(&self, request: Request<proto::Employee>) -> Result<Response<proto::Employee>, Status> {
+        create_row::<Employee, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn get(&self, request: Request<proto::GetEmployeeRequest>) -> Result<Response<proto::Employee>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        get_row::<Employee, _, _>(ctx, request.key, request.include_deleted).await
+    }
+
+    async fn list(&self, request: Request<proto::ListRequest>) -> Result<Response<Self::ListStream>, Status> {
+        list_rows::<Employee, _>(self.context(&request)?, request.into_inner())
+    }
+
+    async fn update(&self, request: Request<proto::UpdateEmployeeRequest>) -> Result<Response<proto::Employee>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        update_row::<Employee, _, _>(ctx, request.key, request.version, request.employee).await
+    }
+
+    async fn delete(&self, request: Request<proto::EmployeeKey>) -> Result<Response<proto::ActionReply>, Status> {
+        delete_row::<Employee, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn restore(&self, request: Request<proto::EmployeeKey>) -> Result<Response<proto::ActionReply>, Status> {
+        restore_row::<Employee, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn purge(&self, request: Request<proto::EmployeeKey>) -> Result<Response<proto::ActionReply>, Status> {
+        purge_row::<Employee, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn terminate(&self, request: Request<proto::TerminateEmployeeRequest>) -> Result<Response<proto::TerminationResult>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        let emp_no = request.emp_no;
+        let result = terminate_employee(&ctx.state, &ctx.audit, emp_no, request.try_into()?).await.map_err(status)?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn rehire(&self, request: Request<proto::RehireEmployeeRequest>) -> Result<Response<proto::RehireResult>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        let emp_no = request.emp_no;
+        let result = rehire_employee(&ctx.state, &ctx.audit, emp_no, request.try_into()?).await.map_err(status)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
+#[tonic::async_trait]
+impl DepartmentService for GrpcService {
+    type ListStream = RowStream<proto::Department>;
+
+    async fn create(&self, request: Request<proto::Department>) -> Result<Response<proto::Department>, Status> {
+        create_row::<Department, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn get(&self, request: Request<proto::GetDepartmentRequest>) -> Result<Response<proto::Department>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        get_row::<Department, _, _>(ctx, request.key, request.include_deleted).await
+    }
+
+    async fn list(&self, request: Request<proto::ListRequest>) -> Result<Response<Self::ListStream>, Status> {
+        list_rows::<Department, _>(self.context(&request)?, request.into_inner())
+    }
+
+    async fn update(&self, request: Request<proto::UpdateDepartmentRequest>) -> Result<Response<proto::Department>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        update_row::<Department, _, _>(ctx, request.key, request.version, request.department).await
+    }
+
+    async fn delete(&self, request: Request<proto::DepartmentKey>) -> Result<Response<proto::ActionReply>, Status> {
+        delete_row::<Department, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn restore(&self, request: Request<proto::DepartmentKey>) -> Result<Response<proto::ActionReply>, Status> {
+        restore_row::<Department, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn purge(&self, request: Request<proto::DepartmentKey>) -> Result<Response<proto::ActionReply>, Status> {
+        purge_row::<Department, _>(self.context(&request)?, request.into_inner()).await
+    }
+}
+
+#[tonic::async_trait]
+impl DeptEmpService for GrpcService {
+    type ListStream = RowStream<proto::DeptEmp>;
+
+    async fn create(&self, request: Request<proto::DeptEmp>) -> Result<Response<proto::DeptEmp>, Status> {
+        create_row::<DeptEmp, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn get(&self, request: Request<proto::GetDeptEmpRequest>) -> Result<Response<proto::DeptEmp>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        get_row::<DeptEmp, _, _>(ctx, request.key, request.include_deleted).await
+    }
+
+    async fn list(&self, request: Request<proto::ListRequest>) -> Result<Response<Self::ListStream>, Status> {
+        list_rows::<DeptEmp, _>(self.context(&request)?, request.into_inner())
+    }
+
+    async fn update(&self, request: Request<proto::UpdateDeptEmpRequest>) -> Result<Response<proto::DeptEmp>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        update_row::<DeptEmp, _, _>(ctx, request.key, request.version, request.dept_emp).await
+    }
+
+    async fn delete(&self, request: Request<proto::DeptEmpKey>) -> Result<Response<proto::ActionReply>, Status> {
+        delete_row::<DeptEmp, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn restore(&self, request: Request<proto::DeptEmpKey>) -> Result<Response<proto::ActionReply>, Status> {
+        restore_row::<DeptEmp, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn purge(&self, request: Request<proto::DeptEmpKey>) -> Result<Response<proto::ActionReply>, Status> {
+        purge_row::<DeptEmp, _>(self.context(&request)?, request.into_inner()).await
+    }
+}
+
+#[tonic::async_trait]
+impl DeptManagerService for GrpcService {
+    type ListStream = RowStream<proto::DeptManager>;
+
+    async fn create(&self, request: Request<proto::DeptManager>) -> Result<Response<proto::DeptManager>, Status> {
+        create_row::<DeptManager, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn get(&self, request: Request<proto::GetDeptManagerRequest>) -> Result<Response<proto::DeptManager>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        get_row::<DeptManager, _, _>(ctx, request.key, request.include_deleted).await
+    }
+
+    async fn list(&self, request: Request<proto::ListRequest>) -> Result<Response<Self::ListStream>, Status> {
+        list_rows::<DeptManager, _>(self.context(&request)?, request.into_inner())
+    }
+
+    async fn update(&self, request: Request<proto::UpdateDeptManagerRequest>) -> Result<Response<proto::DeptManager>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        update_row::<DeptManager, _, _>(ctx, request.key, request.version, request.dept_manager).await
+    }
+
+    async fn delete(&self, request: Request<proto::DeptManagerKey>) -> Result<Response<proto::ActionReply>, Status> {
+        delete_row::<DeptManager, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn restore(&self, request: Request<proto::DeptManagerKey>) -> Result<Response<proto::ActionReply>, Status> {
+        restore_row::<DeptManager, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn purge(&self, request: Request<proto::DeptManagerKey>) -> Result<Response<proto::ActionReply>, Status> {
+        purge_row::<DeptManager, _>(self.context(&request)?, request.into_inner()).await
+    }
+}
+
+#[tonic::async_trait]
+impl TitleService for GrpcService {
+    type ListStream = RowStream<proto::Title>;
+
+    async fn create(&self, request: Request<proto::Title>) -> Result<Response<proto::Title>, Status> {
+        create_row::<Title, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn get(&self, request: Request<proto::GetTitleRequest>) -> Result<Response<proto::Title>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        get_row::<Title, _, _>(ctx, request.key, request.include_deleted).await
+    }
+
+    async fn list(&self, request: Request<proto::ListRequest>) -> Result<Response<Self::ListStream>, Status> {
+        list_rows::<Title, _>(self.context(&request)?, request.into_inner())
+    }
+
+    async fn update(&self, request: Request<proto::UpdateTitleRequest>) -> Result<Response<proto::Title>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        update_row::<Title, _, _>(ctx, request.key, request.version, request.title).await
+    }
+
+    async fn delete(&self, request: Request<proto::TitleKey>) -> Result<Response<proto::ActionReply>, Status> {
+        delete_row::<Title, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn restore(&self, request: Request<proto::TitleKey>) -> Result<Response<proto::ActionReply>, Status> {
+        restore_row::<Title, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn purge(&self, request: Request<proto::TitleKey>) -> Result<Response<proto::ActionReply>, Status> {
+        purge_row::<Title, _>(self.context(&request)?, request.into_inner()).await
+    }
+}
+
+#[tonic::async_trait]
+impl SalaryService for GrpcService {
+    type ListStream = RowStream<proto::Salary>;
+
+    async fn create(&self, request: Request<proto::Salary>) -> Result<Response<proto::Salary>, Status> {
+        create_row::<Salary, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn get(&self, request: Request<proto::GetSalaryRequest>) -> Result<Response<proto::Salary>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        get_row::<Salary, _, _>(ctx, request.key, request.include_deleted).await
+    }
+
+    async fn list(&self, request: Request<proto::ListRequest>) -> Result<Response<Self::ListStream>, Status> {
+        list_rows::<Salary, _>(self.context(&request)?, request.into_inner())
+    }
+
+    async fn update(&self, request: Request<proto::UpdateSalaryRequest>) -> Result<Response<proto::Salary>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        update_row::<Salary, _, _>(ctx, request.key, request.version, request.salary).await
+    }
+
+    async fn delete(&self, request: Request<proto::SalaryKey>) -> Result<Response<proto::ActionReply>, Status> {
+        delete_row::<Salary, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn restore(&self, request: Request<proto::SalaryKey>) -> Result<Response<proto::ActionReply>, Status> {
+        restore_row::<Salary, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn purge(&self, request: Request<proto::SalaryKey>) -> Result<Response<proto::ActionReply>, Status> {
+        purge_row::<Salary, _>(self.context(&request)?, request.into_inner()).await
+    }
+
+    async fn adjust(&self, request: Request<proto::AdjustSalaryRequest>) -> Result<Response<proto::SalaryAdjustmentResult>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        let adjustment = required("adjustment", request.adjustment)?.try_into()?;
+        let result = adjust_salary(&ctx.state, &ctx.audit, request.emp_no, adjustment).await.map_err(status)?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn adjust_department(&self, request: Request<proto::AdjustDepartmentRequest>) -> Result<Response<proto::DepartmentAdjustmentResult>, Status> {
+        let ctx = self.context(&request)?;
+        let request = request.into_inner();
+        let adjustment = required("adjustment", request.adjustment)?.try_into()?;
+        let result = adjust_department_salaries(&ctx.state, &ctx.audit, request.dept_no, adjustment, request.dry_run)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
@@ -0,0 +1,67 @@
+// Health, readiness and build info for load balancers and deploy tooling
+use axum::{
+    extract::{Json, State},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde_json::{json, Value};
+use std::{sync::Arc, time::Duration};
+use tokio::time::timeout;
+use tracing::warn;
+
+use employee_core::db::{self, AppState};
+use employee_core::error::ApiError;
+use employee_core::metrics;
+use employee_core::sodium::sodium_crypto::{key_source, KeySource};
+
+// A readiness probe should answer quickly even when the pool is exhausted.
+const READY_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Liveness: the process is up and serving requests.
+pub async fn healthz_handler() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+// Readiness: the database answers and the configured encryption key was loaded at startup.
+// A temporary generated key is not ready: its ciphertexts do not survive a restart.
+pub async fn readyz_handler(State(state): State<Arc<AppState>>) -> Response {
+    let database = match timeout(READY_TIMEOUT, db::ping(&state.db)).await {
+        Ok(Ok(())) => "ok",
+        Ok(Err(e)) => {
+            warn!(error = ?e, "Readiness: database ping failed");
+            "unreachable"
+        }
+        Err(_) => {
+            warn!("Readiness: database ping timed out");
+            "timeout"
+        }
+    };
+    let encryption_key = match key_source() {
+        Some(KeySource::Configured) => "ok",
+        Some(KeySource::Generated) => "generated",
+        None => "missing",
+    };
+
+    let ready = database == "ok" && encryption_key == "ok";
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    let body = json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "checks": { "database": database, "encryption_key": encryption_key },
+    });
+    (status, Json(body)).into_response()
+}
+
+pub async fn version_handler() -> Json<Value> {
+    Json(json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "git_commit": env!("GIT_COMMIT"),
+        "profile": if cfg!(debug_assertions) { "debug" } else { "release" },
+    }))
+}
+
+// Prometheus text format. Unauthenticated like the probes; keep it off public listeners.
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> Result<Response, ApiError> {
+    let body = metrics::render(&state.db, state.config.database.max_connections).map_err(ApiError::internal)?;
+    Ok(([(CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response())
+}
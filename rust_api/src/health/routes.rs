@@ -0,0 +1,18 @@
+// health/routes.rs
+use axum::{
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::health::handlers::{healthz_handler, metrics_handler, readyz_handler, version_handler};
+
+// Merged in after the rate limiter, so probes and scrapes need no token and don't spend anyone's budget.
+pub fn create_health_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler))
+        .route("/version", get(version_handler))
+        .route("/metrics", get(metrics_handler))
+        .with_state(app_state)
+}
@@ -0,0 +1,47 @@
+// The import subcommand: the same import as POST /import, run against the configured
+// database without going through the server. The report is printed as JSON, not sealed.
+use std::sync::Arc;
+use tracing::error;
+use uuid::Uuid;
+
+use employee_core::audit::AuditContext;
+use employee_core::db::AppState;
+use employee_core::import::models::ImportQuery;
+
+use crate::cli::ImportArgs;
+use crate::import::importer::import_csv;
+
+// The process exit code: 0 when the file went in (or would, on a dry run) without errors.
+pub async fn run(state: Arc<AppState>, args: &ImportArgs) -> i32 {
+    let data = match std::fs::read(&args.file) {
+        Ok(data) => data,
+        Err(e) => {
+            error!(file = %args.file.display(), error = %e, "Import file could not be read");
+            return 2;
+        }
+    };
+    let audit = AuditContext { actor: args.actor.clone(), request_id: Uuid::new_v4().to_string() };
+    let params = ImportQuery { mode: args.mode, dry_run: args.dry_run };
+
+    let report = match import_csv(&state, &audit, args.resource, &data, &params).await {
+        Ok(report) => report,
+        Err(e) => {
+            error!(error = %e, "Import failed");
+            return 2;
+        }
+    };
+
+    let json = serde_json::to_string_pretty(&report).expect("reports always serialize");
+    let written = match &args.report {
+        Some(path) => std::fs::write(path, json + "\n"),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    };
+    if let Err(e) = written {
+        error!(error = %e, "Import report could not be written");
+        return 2;
+    }
+    if report.failed_rows == 0 { 0 } else { 1 }
+}
@@ -0,0 +1,26 @@
+// import/handlers.rs
+use axum::{
+    body::Bytes,
+    extract::{rejection::{PathRejection, QueryRejection}, Path, Query},
+    Json,
+};
+
+use employee_core::error::ApiError;
+use employee_core::import::models::{ImportQuery, ImportResource};
+use employee_core::repository::{crud::encrypted, CrudContext};
+
+use crate::import::importer::import_csv;
+
+// Bulk writes to whole tables, admin only like /export. The body is the CSV file itself, with a
+// header row naming the model's fields.
+pub async fn import_handler(
+    ctx: CrudContext,
+    resource: Result<Path<ImportResource>, PathRejection>,
+    params: Result<Query<ImportQuery>, QueryRejection>,
+    body: Bytes,
+) -> Result<Json<String>, ApiError> {
+    ctx.claims.require_admin()?;
+    let (Path(resource), Query(params)) = (resource?, params?);
+    let report = import_csv(&ctx.state, &ctx.audit, resource, &body, &params).await?;
+    encrypted(&report)
+}
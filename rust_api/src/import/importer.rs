@@ -0,0 +1,312 @@
+// CSV imports, shared by POST /import and the import subcommand. Every row is parsed and
+// validated before anything is written; the valid ones then go through
+// repository::bulk::insert_batch, so database rejections (duplicate keys, unknown
+// employees or departments) land in the same per-row report.
+use chrono::NaiveDate;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use employee_core::audit::AuditContext;
+use employee_core::db::AppState;
+use employee_core::dept_emp::models::DeptEmp;
+use employee_core::employees::models::Employee;
+use employee_core::error::ApiError;
+use employee_core::import::models::{ImportMode, ImportQuery, ImportReport, ImportResource, RowError};
+use employee_core::repository::bulk::{insert_batch, BulkRow, BATCH_ROWS};
+use employee_core::repository::Resource;
+use employee_core::salaries::models::Salary;
+use employee_core::titles::models::Title;
+use employee_core::validation::FieldError;
+
+pub async fn import_csv(
+    state: &Arc<AppState>,
+    audit: &AuditContext,
+    resource: ImportResource,
+    data: &[u8],
+    params: &ImportQuery,
+) -> Result<ImportReport, ApiError> {
+    match resource {
+        ImportResource::Employees => import::<Employee>(state, audit, data, params).await,
+        ImportResource::Salaries => import::<Salary>(state, audit, data, params).await,
+        ImportResource::Titles => import::<Title>(state, audit, data, params).await,
+        ImportResource::DeptEmp => import::<DeptEmp>(state, audit, data, params).await,
+    }
+}
+
+#[instrument(name = "import", skip_all, fields(table = R::TABLE, mode = ?params.mode, dry_run = params.dry_run))]
+async fn import<R: Resource>(state: &Arc<AppState>, audit: &AuditContext, data: &[u8], params: &ImportQuery) -> Result<ImportReport, ApiError> {
+    let Parsed { rows, mut errors, total_rows } = parse::<R>(data)?;
+
+    // All-or-nothing stops at a bad file unless this is a dry run, which should report
+    // everything the database would say as well.
+    let mut committed = false;
+    if errors.is_empty() || params.mode == ImportMode::BestEffort || params.dry_run {
+        let (failures, written) = write(state, audit, &rows, params).await?;
+        committed = written;
+        let keys = rows.iter().map(|bulk| (bulk.line, json!(bulk.row.key()))).collect::<HashMap<_, _>>();
+        errors.extend(failures.into_iter().map(|(line, error)| RowError {
+            line,
+            key: keys.get(&line).cloned(),
+            errors: vec![database_error(error)],
+        }));
+    }
+    errors.sort_by_key(|error| error.line);
+
+    let report = ImportReport {
+        resource: R::TABLE.to_string(),
+        mode: params.mode,
+        dry_run: params.dry_run,
+        committed,
+        total_rows,
+        accepted_rows: accepted_rows(params, committed, total_rows, errors.len()),
+        failed_rows: errors.len(),
+        errors,
+    };
+    info!(total = report.total_rows, accepted = report.accepted_rows, failed = report.failed_rows, committed, "Import finished");
+    Ok(report)
+}
+
+// A rolled-back all-or-nothing import kept nothing. A dry run counts what would have been kept.
+fn accepted_rows(params: &ImportQuery, committed: bool, total_rows: usize, failed_rows: usize) -> usize {
+    if params.mode == ImportMode::AllOrNothing && !params.dry_run && !committed {
+        return 0;
+    }
+    total_rows - failed_rows
+}
+
+struct Parsed<R> {
+    rows: Vec<BulkRow<R>>,
+    errors: Vec<RowError>,
+    total_rows: usize,
+}
+
+// Reads every data row. A malformed file (no header, missing columns) is a 400; problems
+// with single rows go in the report.
+fn parse<R: Resource>(data: &[u8]) -> Result<Parsed<R>, ApiError> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(data);
+    let headers = reader.headers()
+        .map_err(|e| ApiError::bad_request(format!("CSV header could not be read: {}", e)))?
+        .clone();
+    let missing = R::COLUMNS.iter().filter(|column| !headers.iter().any(|header| header == **column)).copied().collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(ApiError::bad_request(format!("CSV header is missing column(s): {}", missing.join(", "))));
+    }
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen = HashMap::new();
+    let mut record = csv::StringRecord::new();
+    let mut total_rows = 0;
+    loop {
+        let line = reader.position().line();
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {}
+            Err(e) => {
+                total_rows += 1;
+                errors.push(row_error(line, None, "row", read_message(&e)));
+                continue;
+            }
+        }
+        // Spreadsheets tend to export trailing blank lines.
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        total_rows += 1;
+
+        // chrono's parse errors don't say which column failed, so dates are checked up front.
+        let bad_dates = headers.iter().zip(record.iter())
+            .filter(|(column, value)| column.ends_with("_date") && !value.is_empty() && NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err())
+            .map(|(column, value)| FieldError { field: column.to_string(), message: format!("expected a YYYY-MM-DD date, got {:?}", value) })
+            .collect::<Vec<_>>();
+        if !bad_dates.is_empty() {
+            errors.push(RowError { line, key: None, errors: bad_dates });
+            continue;
+        }
+
+        let row: R = match record.deserialize(Some(&headers)) {
+            Ok(row) => row,
+            Err(e) => {
+                let field = match e.kind() {
+                    csv::ErrorKind::Deserialize { err, .. } => err.field().and_then(|index| headers.get(index as usize)),
+                    _ => None,
+                };
+                errors.push(row_error(line, None, field.unwrap_or("row"), deserialize_message(&e)));
+                continue;
+            }
+        };
+
+        let key = json!(row.key());
+        // A multi-row INSERT cannot say which allocated number went to which line.
+        if row.generates_key() {
+            errors.push(row_error(line, None, R::KEY_COLUMNS[0], "must be given in imports".to_string()));
+            continue;
+        }
+        if let Err(invalid) = row.validate() {
+            errors.push(RowError { line, key: Some(key), errors: invalid.errors });
+            continue;
+        }
+        if let Some(first) = seen.insert(key.to_string(), line) {
+            errors.push(row_error(line, Some(key), "key", format!("duplicates the row on line {}", first)));
+            continue;
+        }
+        rows.push(BulkRow { line, row });
+    }
+    Ok(Parsed { rows, errors, total_rows })
+}
+
+// Inserts the rows a batch at a time: in one transaction for all-or-nothing, committed per
+// batch for best-effort. Returns the rows the database rejected and whether anything was kept.
+async fn write<R: Resource>(
+    state: &Arc<AppState>,
+    audit: &AuditContext,
+    rows: &[BulkRow<R>],
+    params: &ImportQuery,
+) -> Result<(Vec<(u64, ApiError)>, bool), ApiError> {
+    let mut failures = Vec::new();
+    match params.mode {
+        ImportMode::AllOrNothing => {
+            let mut tx = state.db.begin().await?;
+            for batch in rows.chunks(BATCH_ROWS) {
+                failures.extend(insert_batch(&mut tx, audit, batch).await?);
+            }
+            if failures.is_empty() && !params.dry_run {
+                tx.commit().await?;
+                return Ok((failures, true));
+            }
+            tx.rollback().await?;
+            Ok((failures, false))
+        }
+        ImportMode::BestEffort => {
+            for batch in rows.chunks(BATCH_ROWS) {
+                let mut tx = state.db.begin().await?;
+                failures.extend(insert_batch(&mut tx, audit, batch).await?);
+                if params.dry_run {
+                    tx.rollback().await?;
+                } else {
+                    tx.commit().await?;
+                }
+            }
+            Ok((failures, !params.dry_run))
+        }
+    }
+}
+
+fn row_error(line: u64, key: Option<serde_json::Value>, field: &str, message: String) -> RowError {
+    RowError { line, key, errors: vec![FieldError { field: field.to_string(), message }] }
+}
+
+fn read_message(error: &csv::Error) -> String {
+    match error.kind() {
+        csv::ErrorKind::UnequalLengths { expected_len, len, .. } => format!("has {} fields, the header has {}", len, expected_len),
+        _ => error.to_string(),
+    }
+}
+
+// csv prefixes its messages with the record position, which the report already has.
+fn deserialize_message(error: &csv::Error) -> String {
+    match error.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => err.kind().to_string(),
+        _ => error.to_string(),
+    }
+}
+
+fn database_error(error: ApiError) -> FieldError {
+    let field = if matches!(error, ApiError::Conflict(_)) { "key" } else { "row" };
+    FieldError { field: field.to_string(), message: error.message() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALARY_HEADER: &str = "emp_no,salary,from_date,to_date\n";
+
+    fn parse_salaries(rows: &str) -> Parsed<Salary> {
+        parse::<Salary>(format!("{}{}", SALARY_HEADER, rows).as_bytes()).unwrap()
+    }
+
+    fn first_error(parsed: &Parsed<Salary>) -> (u64, &str, &str) {
+        let error = &parsed.errors[0];
+        (error.line, error.errors[0].field.as_str(), error.errors[0].message.as_str())
+    }
+
+    #[test]
+    fn parse_accepts_valid_rows_and_skips_blank_lines() {
+        let parsed = parse_salaries("10001,60117,1986-06-26,1987-06-26\n10001,62102,1987-06-26,1988-06-25\n,,,\n");
+        assert_eq!(parsed.total_rows, 2);
+        assert!(parsed.errors.is_empty());
+        assert_eq!(parsed.rows.iter().map(|bulk| bulk.line).collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(parsed.rows[1].row.salary, 62102);
+    }
+
+    #[test]
+    fn parse_rejects_a_header_without_every_column() {
+        let error = parse::<Salary>(b"emp_no,salary\n10001,60117\n").err().unwrap();
+        assert_eq!(error.message(), "CSV header is missing column(s): from_date, to_date");
+    }
+
+    #[test]
+    fn parse_names_the_column_of_a_bad_date() {
+        let parsed = parse_salaries("10001,60117,26/06/1986,1987-06-26\n");
+        assert_eq!(first_error(&parsed), (2, "from_date", "expected a YYYY-MM-DD date, got \"26/06/1986\""));
+        assert!(parsed.rows.is_empty());
+    }
+
+    #[test]
+    fn parse_names_the_column_that_does_not_deserialize() {
+        let parsed = parse_salaries("10001,lots,1986-06-26,1987-06-26\n");
+        let (line, field, _) = first_error(&parsed);
+        assert_eq!((line, field), (2, "salary"));
+    }
+
+    #[test]
+    fn parse_reports_rows_with_the_wrong_number_of_fields() {
+        let parsed = parse_salaries("10001,60117,1986-06-26\n10002,65828,1996-08-03,1997-08-03\n");
+        assert_eq!(first_error(&parsed), (2, "row", "has 3 fields, the header has 4"));
+        assert_eq!(parsed.total_rows, 2);
+        assert_eq!(parsed.rows.len(), 1);
+    }
+
+    #[test]
+    fn parse_reports_validation_errors_and_duplicates_with_their_key() {
+        let parsed = parse_salaries("10001,-5,1986-06-26,1987-06-26\n10001,60117,1986-06-26,1987-06-26\n10001,60118,1986-06-26,1987-06-26\n");
+        assert_eq!(parsed.errors.len(), 2);
+        assert_eq!(first_error(&parsed).1, "salary");
+        assert!(parsed.errors[0].key.is_some());
+        assert_eq!((parsed.errors[1].line, parsed.errors[1].errors[0].message.as_str()), (4, "duplicates the row on line 3"));
+    }
+
+    #[test]
+    fn parse_keeps_separate_stints_in_one_department() {
+        let data = b"emp_no,dept_no,from_date,to_date\n10001,d005,1986-06-26,1990-01-01\n10001,d005,1995-01-01,9999-01-01\n";
+        let parsed = parse::<DeptEmp>(data).unwrap();
+        assert!(parsed.errors.is_empty());
+        assert_eq!(parsed.rows.len(), 2);
+    }
+
+    #[test]
+    fn parse_requires_emp_no_for_employees() {
+        let data = b"emp_no,birth_date,first_name,last_name,gender,hire_date\n0,1953-09-02,Georgi,Facello,M,1986-06-26\n";
+        let parsed = parse::<Employee>(data).unwrap();
+        let error = &parsed.errors[0].errors[0];
+        assert_eq!((error.field.as_str(), error.message.as_str()), ("emp_no", "must be given in imports"));
+    }
+
+    #[test]
+    fn database_errors_blame_the_key_only_for_conflicts() {
+        assert_eq!(database_error(ApiError::conflict("Duplicate entry")).field, "key");
+        assert_eq!(database_error(ApiError::bad_request("Unknown employee")).field, "row");
+    }
+
+    #[test]
+    fn accepted_rows_is_zero_when_all_or_nothing_rolls_back() {
+        let params = |mode, dry_run| ImportQuery { mode, dry_run };
+        assert_eq!(accepted_rows(&params(ImportMode::AllOrNothing, false), false, 10, 1), 0);
+        assert_eq!(accepted_rows(&params(ImportMode::AllOrNothing, false), true, 10, 0), 10);
+        assert_eq!(accepted_rows(&params(ImportMode::AllOrNothing, true), false, 10, 1), 9);
+        assert_eq!(accepted_rows(&params(ImportMode::BestEffort, false), true, 10, 1), 9);
+    }
+}
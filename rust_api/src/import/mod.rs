@@ -0,0 +1,4 @@
+pub mod command;
+pub mod handlers;
+pub mod importer;
+pub mod routes;
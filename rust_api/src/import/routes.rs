@@ -0,0 +1,15 @@
+// import/routes.rs
+use axum::{extract::DefaultBodyLimit, routing::post, Router};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::import::handlers::import_handler;
+
+// Room for a few hundred thousand rows; anything bigger belongs on the import subcommand.
+const IMPORT_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
+pub fn create_import_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/{resource}", post(import_handler))
+        .layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT))
+        .with_state(app_state)
+}
@@ -0,0 +1,90 @@
+// The REST surface of the original top-level src/ tree, kept for clients that still use it:
+//   GET /, POST / (bare row), GET /{key...}, PATCH /{key...} (If-Match optional), DELETE /{key...}
+// It runs on the same repository as the action API, so writes are validated and audited and
+// DELETE is a soft delete. Selected with router = "legacy" in the config.
+use axum::{
+    extract::{rejection::{JsonRejection, PathRejection}, Json, Path},
+    http::header::IF_MATCH,
+    response::Response,
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use sqlx::{mysql::MySqlRow, query};
+use std::sync::Arc;
+use tracing::warn;
+
+use employee_core::db::AppState;
+use employee_core::departments::models::Department;
+use employee_core::dept_emp::models::DeptEmp;
+use employee_core::dept_manager::models::DeptManager;
+use employee_core::employees::models::Employee;
+use employee_core::error::ApiError;
+use employee_core::repository::{execute, rest::{self, key_path}, CrudAction, CrudContext, Resource};
+use employee_core::repository::resource::{bind_key, select_sql};
+use employee_core::salaries::models::Salary;
+use employee_core::titles::models::Title;
+use employee_core::validation::parse_request;
+
+pub fn create_legacy_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .nest("/employees", resource_routes::<Employee>(app_state.clone()))
+        .nest("/departments", resource_routes::<Department>(app_state.clone()))
+        .nest("/dept_manager", resource_routes::<DeptManager>(app_state.clone()))
+        .nest("/dept_emp", resource_routes::<DeptEmp>(app_state.clone()))
+        .nest("/titles", resource_routes::<Title>(app_state.clone()))
+        .nest("/salaries", resource_routes::<Salary>(app_state))
+}
+
+fn resource_routes<R: Resource>(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list::<R>).post(create::<R>))
+        .route(&key_path::<R>(), get(read_one::<R>).patch(edit::<R>).delete(remove::<R>))
+        .with_state(app_state)
+}
+
+async fn list<R: Resource>(ctx: CrudContext) -> Result<Response, ApiError> {
+    execute(&ctx, CrudAction::<R>::ReadAll { include_deleted: false }).await
+}
+
+async fn create<R: Resource>(ctx: CrudContext, payload: Result<Json<Value>, JsonRejection>) -> Result<Response, ApiError> {
+    let Json(payload) = payload?;
+    rest::create::<R>(&ctx, payload).await
+}
+
+async fn read_one<R: Resource>(ctx: CrudContext, key: Result<Path<R::Key>, PathRejection>) -> Result<Response, ApiError> {
+    let Path(key) = key?;
+    execute(&ctx, CrudAction::<R>::Read { key, include_deleted: false }).await
+}
+
+// The old PATCH had no concurrency check. An If-Match header is honored like on the action API;
+// without one it updates whatever version is current when the request arrives, so only a write
+// landing in between is rejected (409). That fallback is deprecated.
+async fn edit<R: Resource>(
+    ctx: CrudContext,
+    key: Result<Path<R::Key>, PathRejection>,
+    payload: Result<Json<Value>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let (Path(key), Json(payload)) = (key?, payload?);
+    let row: R = parse_request(payload)?;
+
+    if ctx.headers.contains_key(IF_MATCH) {
+        return execute(&ctx, CrudAction::Update { key, version: None, row }).await;
+    }
+
+    warn!(table = R::TABLE, user = %ctx.claims.sub, "Deprecated PATCH without If-Match; updating the current version");
+    let query_str = select_sql::<R>();
+    let current = bind_key::<R>(&key, query(&query_str))
+        .bind(false)
+        .try_map(|row: MySqlRow| R::from_row(&row))
+        .fetch_optional(&ctx.state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("{} not found", R::NAME)))?;
+
+    execute(&ctx, CrudAction::Update { key, version: Some(current.row_version()), row }).await
+}
+
+async fn remove<R: Resource>(ctx: CrudContext, key: Result<Path<R::Key>, PathRejection>) -> Result<Response, ApiError> {
+    let Path(key) = key?;
+    execute(&ctx, CrudAction::<R>::Delete(key)).await
+}
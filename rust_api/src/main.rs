@@ -1,31 +1,39 @@
-mod db;
 mod employees;
 mod departments;
 mod dept_manager;
 mod dept_emp;
 mod titles;
 mod salaries;
-mod auth;
 mod rate_limit;
-mod sodium;
+mod audit;
+mod legacy;
+mod cli;
+mod health;
+mod openapi;
+mod graphql;
+mod export;
+mod import;
+mod grpc;
+mod batch;
+mod org;
 
 use std::sync::Arc;
 use axum::{
-    extract::{FromRequestParts, Query},
+    extract::Query,
     http::{
-        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-        HeaderValue, Method, StatusCode,
+        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH},
+        HeaderName, HeaderValue, Method,
     },
-    http::request::Parts,
     Router, Json,
 };
-use futures::future::ready;
+use clap::Parser;
 use dotenv::dotenv;
-use tower_http::cors::CorsLayer;
-use crate::auth::{Claims, validate_jwt};
-use crate::db::AppState;
-use serde_json::{json, Value};
-use std::future::Future;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use employee_core::config::{ApiRouter, Config};
+use employee_core::db::{self, AppState};
+use employee_core::error::ApiError;
+use serde_json::Value;
+use tracing::{error, info, warn};
 
 use crate::employees::routes as employee_routes;
 use crate::departments::routes as department_routes;
@@ -33,52 +41,53 @@ use crate::dept_manager::routes as dept_manager_routes;
 use crate::dept_emp::routes as dept_emp_routes;
 use crate::titles::routes as titles_routes;
 use crate::salaries::routes as salaries_routes;
+use crate::audit::routes as audit_routes;
+use crate::health::routes as health_routes;
+use crate::openapi::routes as openapi_routes;
+use crate::graphql::routes as graphql_routes;
+use crate::export::routes as export_routes;
+use crate::import::routes as import_routes;
+use crate::batch::routes as batch_routes;
+use crate::org::routes as org_routes;
+use crate::cli::{Cli, Command};
 
-impl<S> FromRequestParts<S> for Claims
-where
-    S: Send + Sync,
-{
-    type Rejection = (StatusCode, Json<Value>);
-
-    fn from_request_parts(
-        parts: &mut Parts,
-        _state: &S,
-    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
-        ready(
-            parts.headers.get(axum::http::header::AUTHORIZATION)
-                .and_then(|header| header.to_str().ok())
-                .filter(|header_str| header_str.starts_with("Bearer "))
-                .map(|header_str| header_str.trim_start_matches("Bearer "))
-                .map_or(Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Unauthorized"})))), |token| {
-                    match validate_jwt(token) {
-                        Ok(claims) => Ok(claims),
-                        Err(e) => {
-                            match e.kind() {
-                                jsonwebtoken::errors::ErrorKind::ExpiredSignature => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Token expired"})))),
-                                _ => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Unauthorized"})))),
-                            }
-                        }
-                    }
-                }),
-        )
-    }
-}
-
-async fn decrypt_handler(Query(params): Query<std::collections::HashMap<String, String>>) -> Result<Json<Value>, StatusCode> {
-    let mut encrypted_data = params.get("data").ok_or(StatusCode::BAD_REQUEST)?.clone(); // Clone the string so we can modify it.
+async fn decrypt_handler(Query(params): Query<std::collections::HashMap<String, String>>) -> Result<Json<Value>, ApiError> {
+    let mut encrypted_data = params.get("data").ok_or_else(|| ApiError::missing_field("data"))?.clone(); // Clone the string so we can modify it.
     encrypted_data = encrypted_data.replace(" ", "+"); // Replace spaces with '+'
-    println!("Modified Encrypted Data: {}", encrypted_data); // print the modified string.
-    match crate::sodium::sodium_crypto::decrypt_string(&encrypted_data) {
+    match employee_core::sodium::sodium_crypto::decrypt_string(&encrypted_data) {
         Ok(value) => Ok(Json(value)),
         Err(e) => {
-            eprintln!("Decryption error: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            warn!(error = %e, "Decryption failed");
+            Err(ApiError::bad_request("data could not be decrypted"))
         }
     }
 }
 
+fn action_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .nest("/employees", employee_routes::create_employee_routes(app_state.clone()))
+        .nest("/departments", department_routes::create_department_routes(app_state.clone()))
+        .nest("/dept_manager", dept_manager_routes::create_dept_manager_routes(app_state.clone()))
+        .nest("/dept_emp", dept_emp_routes::create_dept_emp_routes(app_state.clone()))
+        .nest("/titles", titles_routes::create_title_routes(app_state.clone()))
+        .nest("/salaries", salaries_routes::create_salary_routes(app_state))
+}
+
 async fn get_sodium_key() -> String {
-    crate::sodium::sodium_crypto::get_key_base64()
+    employee_core::sodium::sodium_crypto::get_key_base64()
+}
+
+// defaults < config file < environment (.env included) < CLI flags, validated as a whole.
+fn load_config(cli: &Cli) -> Config {
+    let config = Config::load(cli.config.as_deref()).and_then(|mut config| {
+        cli.apply(&mut config);
+        config.validate().map(|_| config)
+    });
+    config.unwrap_or_else(|e| {
+        // Logging is configured from this config, so this one goes straight to stderr.
+        eprintln!("💀 {}", e);
+        std::process::exit(1);
+    })
 }
 
 #[tokio::main]
@@ -86,39 +95,75 @@ async fn main() {
     dotenv().ok();  
     sodiumoxide::init().expect("sodiumoxide::init failed"); // Initialize Sodium
 
-    // Force key generation on server startup.
-    crate::sodium::sodium_crypto::initialize_key();
+    let cli = Cli::parse();
+    let config = load_config(&cli);
+    employee_core::telemetry::init(&config.log);
+
+    // Install the configured key (or generate one) before the first request needs it.
+    employee_core::sodium::sodium_crypto::initialize_key(config.crypto.sodium_key.as_deref())
+        .expect("sodium key was validated with the config");
 
-    let pool = db::create_pool().await;
-    let app_state = Arc::new(AppState { db: pool.clone() });
+    let pool = db::create_pool(&config.database).await.unwrap_or_else(|e| {
+        error!(error = %e, "database.url is not usable");
+        std::process::exit(1);
+    });
+    let app_state = Arc::new(AppState { db: pool.clone(), config });
+    if let Some(Command::Import(args)) = &cli.command {
+        std::process::exit(import::command::run(app_state, args).await);
+    }
+    let config = &app_state.config;
 
+    let origins = config.cors.allowed_origins.iter()
+        .map(|origin| origin.parse::<HeaderValue>().expect("origins were validated with the config"))
+        .collect::<Vec<_>>();
     let cors = CorsLayer::new()
-        .allow_origin("http://localhost:8000".parse::<HeaderValue>().unwrap())
-        .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
         .allow_credentials(true)
-        .allow_headers([AUTHORIZATION, ACCEPT, CONTENT_TYPE]);
+        .allow_headers([
+            AUTHORIZATION, ACCEPT, CONTENT_TYPE, IF_MATCH,
+            HeaderName::from_static(employee_core::request_id::REQUEST_ID_HEADER),
+            HeaderName::from_static(employee_core::idempotency::IDEMPOTENCY_KEY_HEADER),
+        ])
+        .expose_headers([
+            ETAG,
+            HeaderName::from_static(employee_core::request_id::REQUEST_ID_HEADER),
+            HeaderName::from_static(employee_core::idempotency::REPLAYED_HEADER),
+        ]);
 
-    let employee_routes = employee_routes::create_employee_routes(app_state.clone());
-    let department_routes = department_routes::create_department_routes(app_state.clone());
-    let dept_manager_routes = dept_manager_routes::create_dept_manager_routes(app_state.clone());
-    let dept_emp_routes = dept_emp_routes::create_dept_emp_routes(app_state.clone());
-    let titles_routes = titles_routes::create_title_routes(app_state.clone());
-    let salaries_routes = salaries_routes::create_salary_routes(app_state.clone());
+    let resource_routes = match config.router {
+        ApiRouter::Legacy => legacy::create_legacy_routes(app_state.clone()),
+        ApiRouter::Actions => action_routes(app_state.clone()),
+    };
+    let audit_routes = audit_routes::create_audit_routes(app_state.clone());
+    let graphql_routes = graphql_routes::create_graphql_routes(app_state.clone());
+    let export_routes = export_routes::create_export_routes(app_state.clone());
+    let import_routes = import_routes::create_import_routes(app_state.clone());
+    let batch_routes = batch_routes::create_batch_routes(app_state.clone());
+    let org_routes = org_routes::create_org_routes(app_state.clone());
 
-    let app = Router::new()
-        .nest_service("/employees", employee_routes)
-        .nest_service("/departments", department_routes)
-        .nest_service("/dept_manager", dept_manager_routes)
-        .nest_service("/dept_emp", dept_emp_routes)
-        .nest_service("/titles", titles_routes)
-        .nest_service("/salaries", salaries_routes)
+    let app = resource_routes
+        .nest("/audit", audit_routes)
+        .nest("/graphql", graphql_routes)
+        .nest("/export", export_routes)
+        .nest("/import", import_routes)
+        .nest("/batch", batch_routes)
+        .nest("/org", org_routes)
         .route("/decrypt", axum::routing::get(decrypt_handler)) // Add the new route
         .route("/sodium_key", axum::routing::get(get_sodium_key)) // Add this line
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), employee_core::idempotency::idempotency_middleware))
         .layer(cors)
-        .with_state(app_state.clone())
-        .layer(axum::middleware::from_fn_with_state(app_state.clone(), crate::rate_limit::rate_limit::rate_limit_middleware));
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), crate::rate_limit::middleware::rate_limit_middleware))
+        .merge(health_routes::create_health_routes(app_state.clone()))
+        .merge(openapi_routes::create_openapi_routes(config.router))
+        .layer(axum::middleware::from_fn(employee_core::metrics::metrics_middleware))
+        .layer(axum::middleware::from_fn(employee_core::request_id::request_id_middleware));
+
+    if config.grpc.enabled {
+        tokio::spawn(grpc::serve(app_state.clone()));
+    }
 
-    println!("🚀 Server started successfully!");
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8000").await.unwrap();
+    info!(addr = %config.listen_addr(), router = ?config.router, "Server started");
+    let listener = tokio::net::TcpListener::bind(config.listen_addr()).await.unwrap();
     axum::serve(listener, app).await.unwrap();
 }
\ No newline at end of file
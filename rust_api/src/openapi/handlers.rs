@@ -0,0 +1,7 @@
+use axum::extract::{Json, State};
+use std::sync::Arc;
+use utoipa::openapi::OpenApi;
+
+pub async fn openapi_handler(State(doc): State<Arc<OpenApi>>) -> Json<OpenApi> {
+    Json(doc.as_ref().clone())
+}
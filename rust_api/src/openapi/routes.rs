@@ -0,0 +1,19 @@
+// openapi/routes.rs
+use axum::{
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use utoipa_redoc::{Redoc, Servable};
+use employee_core::config::ApiRouter;
+use crate::openapi::{handlers::openapi_handler, spec::openapi};
+
+// The spec and its Redoc page at /docs. Merged next to the health routes, so reading the
+// documentation needs no token.
+pub fn create_openapi_routes(router: ApiRouter) -> Router {
+    let doc = openapi(router);
+    Router::new()
+        .route("/openapi.json", get(openapi_handler))
+        .with_state(Arc::new(doc.clone()))
+        .merge(Redoc::with_url("/docs", doc))
+}
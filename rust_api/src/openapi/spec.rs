@@ -0,0 +1,358 @@
+// The OpenAPI 3 document served at /openapi.json. Schemas come from the model derives; the
+// resource paths are generated from each Resource's KEY_COLUMNS, the same way the routers are,
+// so the spec cannot drift from the mounted routes.
+use utoipa::{
+    openapi::{
+        path::{HttpMethod, Operation, OperationBuilder, ParameterBuilder, ParameterIn},
+        request_body::RequestBodyBuilder,
+        schema::{ObjectBuilder, OneOfBuilder, Type},
+        security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
+        Content, OpenApi as OpenApiDoc, Ref, Required, ResponseBuilder,
+    },
+    IntoParams, OpenApi, ToSchema,
+};
+
+use employee_core::audit::{AuditQuery, AuditRecord};
+use employee_core::batch::models::{BatchOperation, BatchRequest, BatchResult, OperationResult, OperationStatus};
+use employee_core::config::ApiRouter;
+use employee_core::departments::models::{Department, DepartmentManagerSummary, DepartmentRequest, DepartmentSummary, SummaryQuery, TitleCount};
+use employee_core::dept_emp::models::{DeptEmp, DeptEmpRequest};
+use employee_core::dept_manager::models::{DeptManager, DeptManagerRequest};
+use employee_core::employees::models::{
+    Employee, EmployeeRequest, RehireEmployee, RehireResult, TerminateEmployee, Termination, TerminationResult,
+};
+use employee_core::error::ErrorBody;
+use employee_core::export::models::{ExportFormat, ExportQuery, ExportResource};
+use employee_core::import::models::{ImportMode, ImportQuery, ImportReport, ImportResource, RowError};
+use employee_core::org::models::{
+    DepartmentManager, DepartmentReports, DirectReports, OrgDepartment, OrgPerson, OrgQuery, OrgTree, OrgTreeFormat, OrgTreeQuery, ReportingLine,
+};
+use employee_core::repository::{rest::key_path, Resource};
+use employee_core::salaries::models::{DepartmentAdjustmentResult, Salary, SalaryAdjustment, SalaryAdjustmentResult, SalaryRequest};
+use employee_core::titles::models::{Title, TitleRequest};
+use employee_core::validation::FieldError;
+
+const BEARER: &str = "bearer_jwt";
+const JSON: &str = "application/json";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Employee database API",
+        description = "Successful responses from the resource and audit routes are a JSON string sealed \
+            with the server's secretbox key (GET /sodium_key). Its plaintext is the schema named in the \
+            response description; GET /decrypt?data=... opens it during development."
+    ),
+    components(schemas(
+        ErrorBody, FieldError,
+        Employee, EmployeeRequest, Termination, TerminateEmployee, RehireEmployee, TerminationResult, RehireResult,
+        Department, DepartmentRequest, DepartmentSummary, DepartmentManagerSummary, TitleCount,
+        DeptEmp, DeptEmpRequest,
+        DeptManager, DeptManagerRequest,
+        Title, TitleRequest,
+        Salary, SalaryRequest, SalaryAdjustment, SalaryAdjustmentResult, DepartmentAdjustmentResult,
+        AuditRecord, AuditQuery,
+        ExportResource, ExportFormat,
+        ImportResource, ImportMode, ImportReport, RowError,
+        BatchRequest, BatchOperation, BatchResult, OperationResult, OperationStatus,
+        OrgPerson, ReportingLine, DepartmentManager, DirectReports, DepartmentReports, OrgTree, OrgDepartment, OrgTreeFormat,
+    ))
+)]
+struct ApiDoc;
+
+// The document for the routes this server mounts with the given router setting.
+pub fn openapi(router: ApiRouter) -> OpenApiDoc {
+    let mut doc = ApiDoc::openapi();
+    if let Some(components) = doc.components.as_mut() {
+        components.add_security_scheme(
+            BEARER,
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+    doc.security = Some(vec![SecurityRequirement::new(BEARER, Vec::<String>::new())]);
+
+    add_resource::<Employee, EmployeeRequest>(&mut doc, router, "/employees");
+    add_resource::<Department, DepartmentRequest>(&mut doc, router, "/departments");
+    add_resource::<DeptManager, DeptManagerRequest>(&mut doc, router, "/dept_manager");
+    add_resource::<DeptEmp, DeptEmpRequest>(&mut doc, router, "/dept_emp");
+    add_resource::<Title, TitleRequest>(&mut doc, router, "/titles");
+    add_resource::<Salary, SalaryRequest>(&mut doc, router, "/salaries");
+    if router == ApiRouter::Actions {
+        add_path(&mut doc, "/departments/summary", HttpMethod::Get, operation("departments", "Manager, headcount, average salary and title breakdown per department, as of a date (default today)")
+            .parameters(Some(SummaryQuery::into_params(|| Some(ParameterIn::Query))))
+            .response("200", sealed("Array of DepartmentSummary"))
+            .errors(&["400", "401", "404", "422", "429", "503"])
+            .build());
+    }
+
+    add_path(&mut doc, "/audit", HttpMethod::Post, operation("audit", "Query the audit log (admin only)")
+        .request_body(Some(json_body(AuditQuery::name().as_ref())))
+        .response("200", sealed("Array of AuditRecord"))
+        .errors(&["400", "401", "403", "422", "429"])
+        .build());
+    add_path(&mut doc, "/export/{resource}", HttpMethod::Get, operation("export", "Stream a whole table, filtered, as CSV, NDJSON or Parquet (admin only, not sealed)")
+        .parameter(ParameterBuilder::new()
+            .name("resource")
+            .parameter_in(ParameterIn::Path)
+            .required(Required::True)
+            .schema(Some(Ref::from_schema_name(ExportResource::name()))))
+        .parameters(Some(ExportQuery::into_params(|| Some(ParameterIn::Query))))
+        .response("200", ResponseBuilder::new()
+            .description("The rows in key order, written as they are read; a failure halfway truncates the body")
+            .content(ExportFormat::Csv.content_type(), Content::new(Some(string())))
+            .content(ExportFormat::Ndjson.content_type(), Content::new(Some(string())))
+            .content(ExportFormat::Parquet.content_type(), Content::new(Some(string()))))
+        .errors(&["400", "401", "403", "422", "429", "503"])
+        .build());
+    add_path(&mut doc, "/import/{resource}", HttpMethod::Post, operation("import", "Import a CSV file (header row, one row per line) with a per-row report (admin only)")
+        .parameter(ParameterBuilder::new()
+            .name("resource")
+            .parameter_in(ParameterIn::Path)
+            .required(Required::True)
+            .schema(Some(Ref::from_schema_name(ImportResource::name()))))
+        .parameters(Some(ImportQuery::into_params(|| Some(ParameterIn::Query))))
+        .request_body(Some(RequestBodyBuilder::new()
+            .required(Some(Required::True))
+            .content("text/csv", Content::new(Some(string())))
+            .build()))
+        .response("200", sealed("ImportReport"))
+        .errors(&["400", "401", "403", "429", "503"])
+        .build());
+    add_path(&mut doc, "/batch", HttpMethod::Post, operation("batch", "Run up to 100 resource actions in order in one transaction; a failed action rolls back the rest")
+        .request_body(Some(json_body(BatchRequest::name().as_ref())))
+        .response("200", sealed("BatchResult"))
+        .errors(&["400", "401", "422", "429", "503"])
+        .build());
+    add_path(&mut doc, "/org/employees/{emp_no}/manager", HttpMethod::Get, operation("org", "The employee's manager in each department they were in on as_of (default today)")
+        .parameter(emp_no_param())
+        .parameters(Some(OrgQuery::into_params(|| Some(ParameterIn::Query))))
+        .response("200", sealed("ReportingLine"))
+        .errors(&["400", "401", "404", "429", "503"])
+        .build());
+    add_path(&mut doc, "/org/employees/{emp_no}/reports", HttpMethod::Get, operation("org", "Everyone in the departments the employee managed on as_of (default today)")
+        .parameter(emp_no_param())
+        .parameters(Some(OrgQuery::into_params(|| Some(ParameterIn::Query))))
+        .response("200", sealed("DirectReports"))
+        .errors(&["400", "401", "404", "429", "503"])
+        .build());
+    add_path(&mut doc, "/org/tree", HttpMethod::Get, operation("org", "The company org chart as a JSON or Graphviz DOT download (admin only, not sealed)")
+        .parameters(Some(OrgTreeQuery::into_params(|| Some(ParameterIn::Query))))
+        .response("200", ResponseBuilder::new()
+            .description("Departments with their manager and employees as of the date")
+            .content(OrgTreeFormat::Json.content_type(), Content::new(Some(Ref::from_schema_name(OrgTree::name()))))
+            .content(OrgTreeFormat::Dot.content_type(), Content::new(Some(string()))))
+        .errors(&["400", "401", "403", "404", "422", "429", "503"])
+        .build());
+    add_path(&mut doc, "/graphql", HttpMethod::Post, operation("graphql", "Read-only GraphQL query over employees, departments, titles and salaries")
+        .request_body(Some(RequestBodyBuilder::new()
+            .required(Some(Required::True))
+            .content(JSON, Content::new(Some(ObjectBuilder::new()
+                .property("query", string())
+                .required("query")
+                .property("operationName", string())
+                .property("variables", ObjectBuilder::new()))))
+            .build()))
+        .response("200", sealed("GraphQL response {data, errors}"))
+        .errors(&["400", "401", "429"])
+        .build());
+    add_path(&mut doc, "/decrypt", HttpMethod::Get, operation("crypto", "Open a sealed response body")
+        .parameter(query_param("data", Type::String, "The sealed string, as returned by the API", true))
+        .response("200", ResponseBuilder::new().description("The plaintext JSON").content(JSON, Content::new(Some(ObjectBuilder::new()))))
+        .errors(&["400", "401", "429"])
+        .build());
+    add_path(&mut doc, "/sodium_key", HttpMethod::Get, operation("crypto", "The base64 secretbox key responses are sealed with")
+        .response("200", ResponseBuilder::new().description("Base64 key").content("text/plain", Content::new(Some(string()))))
+        .errors(&["401", "429"])
+        .build());
+
+    add_probe(&mut doc, "/healthz", "Liveness: the process is serving requests", JSON);
+    add_probe(&mut doc, "/readyz", "Readiness: the database answers and the encryption key is loaded (503 otherwise)", JSON);
+    add_probe(&mut doc, "/version", "Package name, version, git commit and build profile", JSON);
+    add_probe(&mut doc, "/metrics", "Prometheus metrics", "text/plain");
+    add_probe(&mut doc, "/openapi.json", "This document", JSON);
+    add_idempotency_key(&mut doc);
+    doc
+}
+
+// Every write route honours Idempotency-Key (see employee_core::idempotency).
+fn add_idempotency_key(doc: &mut OpenApiDoc) {
+    for item in doc.paths.paths.values_mut() {
+        for operation in [&mut item.post, &mut item.put, &mut item.patch, &mut item.delete].into_iter().flatten() {
+            operation.parameters.get_or_insert_with(Vec::new).push(ParameterBuilder::new()
+                .name("Idempotency-Key")
+                .parameter_in(ParameterIn::Header)
+                .required(Required::False)
+                .description(Some("Unique per write; a retry with the same key and request gets the first response back \
+                    (marked Idempotent-Replayed: true) instead of running again"))
+                .schema(Some(string()))
+                .build());
+        }
+    }
+}
+
+// POST / takes either an action request or, without an "action" field, the row itself (REST create).
+fn add_resource<R, A>(doc: &mut OpenApiDoc, router: ApiRouter, prefix: &str)
+where
+    R: Resource + ToSchema,
+    R::Key: IntoParams,
+    A: ToSchema,
+{
+    let tag = prefix.trim_start_matches('/');
+    let name = R::name();
+    let key = format!("{}{}", prefix, key_path::<R>());
+    let key_params = || R::Key::into_params(|| Some(ParameterIn::Path));
+
+    let list = operation(tag, &format!("List {} rows (at most 10)", name));
+    let read = operation(tag, &format!("Read one {} row; the ETag is its row_version", name)).parameters(Some(key_params()));
+    let remove = operation(tag, &format!("Soft delete a {} row", name)).parameters(Some(key_params()));
+
+    match router {
+        ApiRouter::Actions => {
+            let create_body = OneOfBuilder::new().item(Ref::from_schema_name(A::name())).item(Ref::from_schema_name(name.clone()));
+            add_path(doc, prefix, HttpMethod::Post, operation(tag, &format!("Run a {} action, or create a row when the body has no \"action\"", A::name()))
+                .request_body(Some(RequestBodyBuilder::new().required(Some(Required::True)).content(JSON, Content::new(Some(create_body))).build()))
+                .response("200", sealed("The action's result"))
+                .response("201", sealed(&format!("The created {}", name)))
+                .errors(&["400", "401", "403", "404", "409", "412", "422", "428", "429", "503"])
+                .build());
+            add_path(doc, prefix, HttpMethod::Get, list
+                .parameter(include_deleted())
+                .response("200", sealed(&format!("Array of {}", name)))
+                .errors(&["400", "401", "403", "429", "503"])
+                .build());
+            add_path(doc, &key, HttpMethod::Get, read
+                .parameter(include_deleted())
+                .response("200", sealed(&name))
+                .errors(&["400", "401", "403", "404", "429", "503"])
+                .build());
+            add_path(doc, &key, HttpMethod::Put, operation(tag, &format!("Replace a {} row; If-Match must carry its ETag", name))
+                .parameters(Some(key_params()))
+                .parameter(ParameterBuilder::new().name("If-Match").parameter_in(ParameterIn::Header).required(Required::True).schema(Some(string())))
+                .request_body(Some(json_body(&name)))
+                .response("200", sealed(&format!("The updated {}", name)))
+                .errors(&["400", "401", "404", "409", "412", "422", "428", "429", "503"])
+                .build());
+            add_path(doc, &key, HttpMethod::Delete, remove
+                .parameter(query_param("purge", Type::Boolean, "Delete the row for good (admin only)", false))
+                .response("200", sealed("Rows affected"))
+                .errors(&["400", "401", "403", "404", "409", "429", "503"])
+                .build());
+            add_path(doc, &format!("{}/restore", key), HttpMethod::Post, operation(tag, &format!("Restore a soft-deleted {} row", name))
+                .parameters(Some(key_params()))
+                .response("200", sealed(&format!("The restored {}", name)))
+                .errors(&["400", "401", "404", "409", "429", "503"])
+                .build());
+        }
+        ApiRouter::Legacy => {
+            add_path(doc, prefix, HttpMethod::Post, operation(tag, &format!("Create a {} row", name))
+                .request_body(Some(json_body(&name)))
+                .response("201", sealed(&format!("The created {}", name)))
+                .errors(&["400", "401", "409", "422", "429", "503"])
+                .build());
+            add_path(doc, prefix, HttpMethod::Get, list
+                .response("200", sealed(&format!("Array of {}", name)))
+                .errors(&["401", "429", "503"])
+                .build());
+            add_path(doc, &key, HttpMethod::Get, read
+                .response("200", sealed(&name))
+                .errors(&["400", "401", "404", "429", "503"])
+                .build());
+            add_path(doc, &key, HttpMethod::Patch, operation(tag, &format!("Update a {} row", name))
+                .parameters(Some(key_params()))
+                .request_body(Some(json_body(&name)))
+                .response("200", sealed(&format!("The updated {}", name)))
+                .errors(&["400", "401", "404", "409", "422", "429", "503"])
+                .build());
+            add_path(doc, &key, HttpMethod::Delete, remove
+                .response("200", sealed("Rows affected"))
+                .errors(&["400", "401", "404", "409", "429", "503"])
+                .build());
+        }
+    }
+}
+
+fn add_path(doc: &mut OpenApiDoc, path: &str, method: HttpMethod, operation: Operation) {
+    doc.paths.add_path_operation(path, vec![method], operation);
+}
+
+// Probes and the spec itself are mounted outside the JWT check.
+fn add_probe(doc: &mut OpenApiDoc, path: &str, summary: &str, content_type: &str) {
+    let body = Content::new(Some(if content_type == JSON { ObjectBuilder::new().into() } else { string() }));
+    add_path(doc, path, HttpMethod::Get, operation("service", summary)
+        .securities(Some(Vec::new()))
+        .response("200", ResponseBuilder::new().description("OK").content(content_type, body))
+        .build());
+}
+
+fn operation(tag: &str, summary: &str) -> OperationBuilder {
+    OperationBuilder::new().tag(tag).summary(Some(summary))
+}
+
+fn sealed(plaintext: &str) -> ResponseBuilder {
+    ResponseBuilder::new()
+        .description(format!("Sealed JSON string; plaintext: {}", plaintext))
+        .content(JSON, Content::new(Some(string())))
+}
+
+// Every documented error shares the ErrorBody envelope.
+trait ErrorResponses {
+    fn errors(self, statuses: &[&str]) -> Self;
+}
+
+impl ErrorResponses for OperationBuilder {
+    fn errors(self, statuses: &[&str]) -> Self {
+        statuses.iter().fold(self, |operation, status| {
+            operation.response(*status, ResponseBuilder::new()
+                .description(error_description(status))
+                .content(JSON, Content::new(Some(Ref::from_schema_name(ErrorBody::name())))))
+        })
+    }
+}
+
+fn error_description(status: &str) -> &'static str {
+    match status {
+        "400" => "bad_request: malformed body, path or query",
+        "401" => "unauthorized: missing, invalid or expired token",
+        "403" => "forbidden: admin role required",
+        "404" => "not_found",
+        "409" => "conflict or version_conflict (details holds the current row), or the Idempotency-Key is still in use",
+        "412" => "version_conflict: If-Match is stale (details holds the current row)",
+        "422" => "validation_failed (details lists field errors) or unprocessable (e.g. an Idempotency-Key reused for another request)",
+        "428" => "precondition_required: no If-Match header or version field",
+        "429" => "rate_limited",
+        "503" => "unavailable: the database is busy",
+        _ => "error",
+    }
+}
+
+fn json_body(schema: &str) -> utoipa::openapi::request_body::RequestBody {
+    RequestBodyBuilder::new()
+        .required(Some(Required::True))
+        .content(JSON, Content::new(Some(Ref::from_schema_name(schema))))
+        .build()
+}
+
+fn emp_no_param() -> ParameterBuilder {
+    ParameterBuilder::new()
+        .name("emp_no")
+        .parameter_in(ParameterIn::Path)
+        .required(Required::True)
+        .schema(Some(ObjectBuilder::new().schema_type(Type::Integer)))
+}
+
+fn include_deleted() -> ParameterBuilder {
+    query_param("include_deleted", Type::Boolean, "Include soft-deleted rows (admin only)", false)
+}
+
+fn query_param(name: &str, schema_type: Type, description: &str, required: bool) -> ParameterBuilder {
+    ParameterBuilder::new()
+        .name(name)
+        .parameter_in(ParameterIn::Query)
+        .required(if required { Required::True } else { Required::False })
+        .description(Some(description))
+        .schema(Some(ObjectBuilder::new().schema_type(schema_type)))
+}
+
+fn string() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+    ObjectBuilder::new().schema_type(Type::String).into()
+}
@@ -0,0 +1,101 @@
+// org/dot.rs
+use std::fmt::Write;
+
+use employee_core::org::models::{OrgPerson, OrgTree};
+
+// The tree as a Graphviz digraph: company -> departments -> managers -> employees. Node ids
+// come from dept_no and emp_no ("dept_d005", "emp_110022"), so charts for two dates diff
+// cleanly. Someone in two departments is one node with an edge from each.
+pub fn render(tree: &OrgTree) -> String {
+    let mut dot = String::new();
+    // Writing to a String cannot fail.
+    let _ = writeln!(dot, "digraph org {{");
+    let _ = writeln!(dot, "  graph [label=\"Org chart as of {}\", labelloc=t];", tree.as_of);
+    let _ = writeln!(dot, "  node [shape=box, style=rounded];");
+    let _ = writeln!(dot, "  company [label=\"Company\", shape=doubleoctagon];");
+
+    for department in &tree.departments {
+        let dept_id = format!("dept_{}", escape(&department.dept_no));
+        let _ = writeln!(dot, "  \"{}\" [label=\"{}\\n{}\", shape=folder];", dept_id, escape(&department.dept_no), escape(&department.dept_name));
+        let _ = writeln!(dot, "  company -> \"{}\";", dept_id);
+
+        // Without a manager the employees hang off the department itself.
+        let head = match &department.manager {
+            Some(manager) => {
+                person_node(&mut dot, manager, true);
+                let _ = writeln!(dot, "  \"{}\" -> \"emp_{}\";", dept_id, manager.emp_no);
+                format!("emp_{}", manager.emp_no)
+            }
+            None => dept_id,
+        };
+        for employee in &department.employees {
+            person_node(&mut dot, employee, false);
+            let _ = writeln!(dot, "  \"{}\" -> \"emp_{}\";", head, employee.emp_no);
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn person_node(dot: &mut String, person: &OrgPerson, manager: bool) {
+    let mut label = format!("{} {}\\n#{}", escape(&person.first_name), escape(&person.last_name), person.emp_no);
+    if let Some(title) = &person.title {
+        label.push_str("\\n");
+        label.push_str(&escape(title));
+    }
+    let style = if manager { ", style=\"rounded,bold\"" } else { "" };
+    let _ = writeln!(dot, "  \"emp_{}\" [label=\"{}\"{}];", person.emp_no, label, style);
+}
+
+// Quoted DOT strings only treat the quote and backslash specially.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use employee_core::org::models::OrgDepartment;
+
+    fn person(emp_no: i32, first_name: &str, last_name: &str, title: Option<&str>) -> OrgPerson {
+        OrgPerson { emp_no, first_name: first_name.to_string(), last_name: last_name.to_string(), title: title.map(str::to_string) }
+    }
+
+    fn tree(manager: Option<OrgPerson>, employees: Vec<OrgPerson>) -> OrgTree {
+        OrgTree {
+            as_of: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            departments: vec![OrgDepartment { dept_no: "d005".to_string(), dept_name: "Development".to_string(), manager, employees }],
+        }
+    }
+
+    #[test]
+    fn escape_quotes_and_backslashes() {
+        assert_eq!(escape(r#"R&D "Labs" \ West"#), r#"R&D \"Labs\" \\ West"#);
+        assert_eq!(escape("plain"), "plain");
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let dot = render(&tree(Some(person(110022, "Margareta", "O\"Brien", Some("Manager \\ Lead"))), vec![]));
+        assert!(dot.contains(r#"  "emp_110022" [label="Margareta O\"Brien\n#110022\nManager \\ Lead", style="rounded,bold"];"#), "{}", dot);
+    }
+
+    #[test]
+    fn employees_report_to_the_manager() {
+        let dot = render(&tree(Some(person(110022, "Margareta", "Markovitch", None)), vec![person(10001, "Georgi", "Facello", Some("Engineer"))]));
+        assert!(dot.starts_with("digraph org {\n  graph [label=\"Org chart as of 2000-01-01\", labelloc=t];\n"), "{}", dot);
+        assert!(dot.contains("  company -> \"dept_d005\";\n  \"emp_110022\""), "{}", dot);
+        assert!(dot.contains("  \"dept_d005\" -> \"emp_110022\";\n"), "{}", dot);
+        assert!(dot.contains("  \"emp_10001\" [label=\"Georgi Facello\\n#10001\\nEngineer\"];\n  \"emp_110022\" -> \"emp_10001\";\n"), "{}", dot);
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn employees_hang_off_a_department_without_a_manager() {
+        let dot = render(&tree(None, vec![person(10001, "Georgi", "Facello", None)]));
+        assert!(dot.contains("  \"dept_d005\" -> \"emp_10001\";\n"), "{}", dot);
+        assert!(!dot.contains("rounded,bold"), "{}", dot);
+    }
+}
@@ -0,0 +1,231 @@
+// org/handlers.rs
+use axum::{
+    body::Body,
+    extract::{rejection::{PathRejection, QueryRejection}, Json, Path, Query},
+    http::header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::Response,
+};
+use chrono::{Local, NaiveDate};
+use sqlx::{query_as, FromRow, MySql, Transaction};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use employee_core::db::IN_EFFECT;
+use employee_core::error::ApiError;
+use employee_core::org::models::{
+    DepartmentManager, DepartmentReports, DirectReports, OrgDepartment, OrgPerson, OrgQuery, OrgTree, OrgTreeFormat, OrgTreeQuery, ReportingLine,
+};
+use employee_core::repository::{crud::encrypted, CrudContext};
+use employee_core::validation::Validate;
+
+use crate::org::dot;
+
+// Who the employee reported to on as_of: the manager of each department they were in.
+#[instrument(name = "org.manager", skip_all)]
+pub async fn manager_handler(
+    ctx: CrudContext,
+    emp_no: Result<Path<i32>, PathRejection>,
+    params: Result<Query<OrgQuery>, QueryRejection>,
+) -> Result<Json<String>, ApiError> {
+    let (Path(emp_no), Query(params)) = (emp_no?, params?);
+    let as_of = as_of_or_today(params.as_of);
+
+    let mut tx = ctx.state.db.begin().await?;
+    let employee = person(&mut tx, as_of, emp_no).await?;
+    let memberships = members(&mut tx, as_of, Members::Employee(emp_no)).await?;
+    if memberships.is_empty() {
+        return Err(ApiError::not_found(format!("Employee {} was not in any department on {}", emp_no, as_of)));
+    }
+    let names = departments(&mut tx, None).await?.into_iter().collect::<HashMap<_, _>>();
+    let managers = managers(&mut tx, as_of).await?;
+    tx.commit().await?;
+
+    let managers = memberships.into_iter()
+        .filter_map(|member| {
+            let dept_name = names.get(&member.dept_no)?.clone();
+            let manager = managers.get(&member.dept_no).filter(|manager| manager.emp_no != emp_no).cloned();
+            Some(DepartmentManager { dept_no: member.dept_no, dept_name, manager })
+        })
+        .collect();
+    encrypted(&ReportingLine { employee, as_of, managers })
+}
+
+// Everyone in the departments the employee managed on as_of.
+#[instrument(name = "org.reports", skip_all)]
+pub async fn reports_handler(
+    ctx: CrudContext,
+    emp_no: Result<Path<i32>, PathRejection>,
+    params: Result<Query<OrgQuery>, QueryRejection>,
+) -> Result<Json<String>, ApiError> {
+    let (Path(emp_no), Query(params)) = (emp_no?, params?);
+    let as_of = as_of_or_today(params.as_of);
+
+    let mut tx = ctx.state.db.begin().await?;
+    let manager = person(&mut tx, as_of, emp_no).await?;
+    let managers = managers(&mut tx, as_of).await?;
+    let mut reports = Vec::new();
+    for (dept_no, dept_name) in departments(&mut tx, None).await? {
+        if managers.get(&dept_no).is_none_or(|manager| manager.emp_no != emp_no) {
+            continue;
+        }
+        let members = members(&mut tx, as_of, Members::Department(&dept_no)).await?;
+        let people = members.into_iter().map(|member| member.person).filter(|person| person.emp_no != emp_no).collect();
+        reports.push(DepartmentReports { dept_no, dept_name, reports: people });
+    }
+    tx.commit().await?;
+
+    encrypted(&DirectReports { manager, as_of, departments: reports })
+}
+
+// The whole company (or one department) as a download, admin only. Like /export it is a file
+// for other tools, so the body is plain JSON or DOT rather than sealed.
+#[instrument(name = "org.tree", skip_all)]
+pub async fn tree_handler(
+    ctx: CrudContext,
+    params: Result<Query<OrgTreeQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    ctx.claims.require_admin()?;
+    let Query(params) = params?;
+    params.validate()?;
+    let as_of = as_of_or_today(params.as_of);
+    let dept_no = params.dept_no.as_deref();
+
+    let mut tx = ctx.state.db.begin().await?;
+    let departments = departments(&mut tx, dept_no).await?;
+    if departments.is_empty() && dept_no.is_some() {
+        return Err(ApiError::not_found("Department not found"));
+    }
+    let mut managers = managers(&mut tx, as_of).await?;
+    let filter = dept_no.map_or(Members::All, Members::Department);
+    let mut employees: HashMap<String, Vec<OrgPerson>> = HashMap::new();
+    for member in members(&mut tx, as_of, filter).await? {
+        employees.entry(member.dept_no).or_default().push(member.person);
+    }
+    tx.commit().await?;
+
+    let departments = departments.into_iter()
+        .map(|(dept_no, dept_name)| {
+            let manager = managers.remove(&dept_no);
+            let mut people = employees.remove(&dept_no).unwrap_or_default();
+            if let Some(manager) = &manager {
+                people.retain(|person| person.emp_no != manager.emp_no);
+            }
+            OrgDepartment { dept_no, dept_name, manager, employees: people }
+        })
+        .collect();
+    let tree = OrgTree { as_of, departments };
+
+    let body = match params.format {
+        OrgTreeFormat::Json => serde_json::to_vec(&tree).map_err(ApiError::internal)?,
+        OrgTreeFormat::Dot => dot::render(&tree).into_bytes(),
+    };
+    let filename = match dept_no {
+        Some(dept_no) => format!("org-{}-{}.{}", dept_no, as_of, params.format.extension()),
+        None => format!("org-{}.{}", as_of, params.format.extension()),
+    };
+    Response::builder()
+        .header(CONTENT_TYPE, params.format.content_type())
+        .header(CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename))
+        .header(CACHE_CONTROL, "no-store")
+        .body(Body::from(body))
+        .map_err(ApiError::internal)
+}
+
+fn as_of_or_today(as_of: Option<NaiveDate>) -> NaiveDate {
+    as_of.unwrap_or_else(|| Local::now().date_naive())
+}
+
+// The employee columns of OrgPerson plus their title on as_of (two placeholders).
+fn person_columns() -> String {
+    format!(
+        "e.emp_no, e.first_name, e.last_name, \
+         (SELECT t.title FROM titles t WHERE t.emp_no = e.emp_no AND t.deleted_at IS NULL AND t.{} \
+          ORDER BY t.from_date DESC LIMIT 1) AS title",
+        IN_EFFECT,
+    )
+}
+
+#[derive(FromRow)]
+struct Member {
+    dept_no: String,
+    #[sqlx(flatten)]
+    person: OrgPerson,
+}
+
+enum Members<'a> {
+    All,
+    Employee(i32),
+    Department(&'a str),
+}
+
+#[instrument(name = "db.org_person", skip_all)]
+async fn person(tx: &mut Transaction<'_, MySql>, as_of: NaiveDate, emp_no: i32) -> Result<OrgPerson, ApiError> {
+    let query_str = format!("SELECT {} FROM employees e WHERE e.emp_no = ? AND e.deleted_at IS NULL", person_columns());
+    query_as::<_, OrgPerson>(&query_str)
+        .bind(as_of)
+        .bind(as_of)
+        .bind(emp_no)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Employee not found"))
+}
+
+// (dept_no, dept_name) in dept_no order.
+#[instrument(name = "db.org_departments", skip_all)]
+async fn departments(tx: &mut Transaction<'_, MySql>, dept_no: Option<&str>) -> Result<Vec<(String, String)>, ApiError> {
+    let filter = if dept_no.is_some() { " AND dept_no = ?" } else { "" };
+    let query_str = format!("SELECT dept_no, dept_name FROM departments WHERE deleted_at IS NULL{} ORDER BY dept_no", filter);
+    let mut query = query_as::<_, (String, String)>(&query_str);
+    if let Some(dept_no) = dept_no {
+        query = query.bind(dept_no);
+    }
+    Ok(query.fetch_all(&mut **tx).await?)
+}
+
+// Each department's manager on as_of; when appointments overlap, the latest one.
+#[instrument(name = "db.org_managers", skip_all)]
+async fn managers(tx: &mut Transaction<'_, MySql>, as_of: NaiveDate) -> Result<HashMap<String, OrgPerson>, ApiError> {
+    let query_str = format!(
+        "SELECT dm.dept_no, {} FROM dept_manager dm \
+         INNER JOIN employees e ON e.emp_no = dm.emp_no AND e.deleted_at IS NULL \
+         WHERE dm.deleted_at IS NULL AND dm.{} \
+         ORDER BY dm.dept_no, dm.from_date",
+        person_columns(), IN_EFFECT,
+    );
+    let rows = query_as::<_, Member>(&query_str)
+        .bind(as_of)
+        .bind(as_of)
+        .bind(as_of)
+        .bind(as_of)
+        .fetch_all(&mut **tx)
+        .await?;
+    Ok(rows.into_iter().map(|row| (row.dept_no, row.person)).collect())
+}
+
+// dept_emp rows in effect on as_of, in (dept_no, emp_no) order.
+#[instrument(name = "db.org_members", skip_all)]
+async fn members(tx: &mut Transaction<'_, MySql>, as_of: NaiveDate, filter: Members<'_>) -> Result<Vec<Member>, ApiError> {
+    let condition = match filter {
+        Members::All => "",
+        Members::Employee(_) => " AND de.emp_no = ?",
+        Members::Department(_) => " AND de.dept_no = ?",
+    };
+    let query_str = format!(
+        "SELECT de.dept_no, {} FROM dept_emp de \
+         INNER JOIN employees e ON e.emp_no = de.emp_no AND e.deleted_at IS NULL \
+         WHERE de.deleted_at IS NULL AND de.{}{} \
+         ORDER BY de.dept_no, e.emp_no",
+        person_columns(), IN_EFFECT, condition,
+    );
+    let query = query_as::<_, Member>(&query_str)
+        .bind(as_of)
+        .bind(as_of)
+        .bind(as_of)
+        .bind(as_of);
+    let query = match filter {
+        Members::All => query,
+        Members::Employee(emp_no) => query.bind(emp_no),
+        Members::Department(dept_no) => query.bind(dept_no),
+    };
+    Ok(query.fetch_all(&mut **tx).await?)
+}
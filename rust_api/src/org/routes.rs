@@ -0,0 +1,13 @@
+// org/routes.rs
+use axum::{routing::get, Router};
+use std::sync::Arc;
+use employee_core::db::AppState;
+use crate::org::handlers::{manager_handler, reports_handler, tree_handler};
+
+pub fn create_org_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/employees/{emp_no}/manager", get(manager_handler))
+        .route("/employees/{emp_no}/reports", get(reports_handler))
+        .route("/tree", get(tree_handler))
+        .with_state(app_state)
+}
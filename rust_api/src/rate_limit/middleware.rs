@@ -1,22 +1,25 @@
 use axum::{
     extract::State,
-    http::StatusCode,
-    Json,
     middleware::Next,
 };
-use serde_json::{json, Value};
 use sqlx::{query, query_as};
 use std::sync::Arc;
+use tracing::instrument;
 
-use crate::db::{AppState, RateLimit};
-use crate::auth::Claims;
+use employee_core::db::{AppState, RateLimit};
+use employee_core::auth::Claims;
+use employee_core::error::ApiError;
 
 pub async fn rate_limit_middleware( 
     State(app_state): State<Arc<AppState>>,
     claims: Claims,
     request: axum::http::Request<axum::body::Body>, 
     next: Next, 
-) -> Result<axum::response::Response, (StatusCode, Json<Value>)> {
+) -> Result<axum::response::Response, ApiError> {
+    if !app_state.config.rate_limit.enabled {
+        return Ok(next.run(request).await);
+    }
+
     let username = claims.sub;
 
     let rate_limit_result = query_rate_limit(&app_state, &username).await;
@@ -24,20 +27,22 @@ pub async fn rate_limit_middleware(
     match rate_limit_result {
         Ok(rate_limit) => {
             if rate_limit.remaining_requests <= 0 {
-                return Err((StatusCode::TOO_MANY_REQUESTS, Json(json!({"error": "Rate limit exceeded"}))));
+                employee_core::metrics::rate_limited();
+                return Err(ApiError::TooManyRequests("Rate limit exceeded".to_string()));
             }
 
-            update_rate_limit(&app_state, &username, rate_limit.remaining_requests - 1).await.map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Internal server error"}))))?;
+            update_rate_limit(&app_state, &username, rate_limit.remaining_requests - 1).await?;
 
             Ok(next.run(request).await)
         }
         Err(_) => {
-            create_new_rate_limit(&app_state, &username).await.map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Internal server error"}))))?;
+            create_new_rate_limit(&app_state, &username).await?;
             Ok(next.run(request).await)
         }
     }
 }
 
+#[instrument(name = "db.rate_limit", skip_all)]
 async fn query_rate_limit(app_state: &AppState, username: &str) -> Result<RateLimit, sqlx::Error> {
     query_as!(
         RateLimit,
@@ -48,6 +53,7 @@ async fn query_rate_limit(app_state: &AppState, username: &str) -> Result<RateLi
     .await
 }
 
+#[instrument(name = "db.rate_limit", skip_all)]
 async fn update_rate_limit(app_state: &AppState, username: &str, remaining_requests: i32) -> Result<(), sqlx::Error> {
     query!(
         "UPDATE rate_limits SET remaining_requests = ? WHERE username = ?",
@@ -59,8 +65,9 @@ async fn update_rate_limit(app_state: &AppState, username: &str, remaining_reque
     .map(|_| ())
 }
 
+#[instrument(name = "db.rate_limit", skip_all)]
 async fn create_new_rate_limit(app_state: &AppState, username: &str) -> Result<(), sqlx::Error>{
-    let initial_limit = 10; // Set request limit
+    let initial_limit = app_state.config.rate_limit.requests;
     query!(
         "INSERT INTO rate_limits (username, remaining_requests) VALUES (?, ?)",
         username,
@@ -1 +1 @@
-pub mod rate_limit;
\ No newline at end of file
+pub mod middleware;
@@ -1,145 +1,298 @@
 // salaries/handlers.rs
-use axum::{extract::{State, Json}, http::StatusCode};
+use axum::{extract::Json, response::{IntoResponse, Response}};
 use std::sync::Arc;
-use serde_json::{Value, from_value, to_value};
+use serde_json::{json, Value};
 
-use crate::db::AppState;
-use crate::salaries::models::Salary;
-use crate::sodium::sodium_crypto::{encrypt_json, get_key};
-use crate::auth::Claims;
-use sqlx::{query, query_as};
+use employee_core::error::ApiError;
+use employee_core::repository::{crud::encrypted, execute, rest::{self, is_action_request}, CrudContext};
+use employee_core::db::{AppState, initial_row_version, open_end_date};
+use employee_core::salaries::models::{DepartmentAdjustmentResult, Salary, SalaryAdjustment, SalaryAdjustmentResult, SalaryRequest};
+use employee_core::audit::AuditContext;
+use employee_core::validation::{parse_request, Validate, ValidationErrors};
+use sqlx::{query, query_as, MySql, MySqlConnection, Transaction};
 use chrono::NaiveDate;
+use tracing::{error, instrument};
 
 // CRUD operations using POST
 pub async fn salary_crud_handler(
-    _claims: Claims,
-    State(state): State<Arc<AppState>>,
+    ctx: CrudContext,
     Json(payload): Json<Value>,
-) -> Result<Json<String>, StatusCode> {
-    let action = payload["action"].as_str().ok_or(StatusCode::BAD_REQUEST)?;
-
-    match action {
-        "create" => create_salary(&state, payload["salary"].clone()).await,
-        "read_all" => read_all_salaries(&state).await,
-        "read" => read_salary(&state, payload["emp_no"].as_i64().ok_or(StatusCode::BAD_REQUEST)? as i32, NaiveDate::parse_from_str(payload["from_date"].as_str().ok_or(StatusCode::BAD_REQUEST)?, "%Y-%m-%d").map_err(|_| StatusCode::BAD_REQUEST)?).await,
-        "update" => update_salary(&state, payload["emp_no"].as_i64().ok_or(StatusCode::BAD_REQUEST)? as i32, NaiveDate::parse_from_str(payload["from_date"].as_str().ok_or(StatusCode::BAD_REQUEST)?, "%Y-%m-%d").map_err(|_| StatusCode::BAD_REQUEST)?, payload["salary"].clone()).await,
-        "delete" => delete_salary(&state, payload["emp_no"].as_i64().ok_or(StatusCode::BAD_REQUEST)? as i32, NaiveDate::parse_from_str(payload["from_date"].as_str().ok_or(StatusCode::BAD_REQUEST)?, "%Y-%m-%d").map_err(|_| StatusCode::BAD_REQUEST)?).await,
-        _ => Err(StatusCode::BAD_REQUEST),
+) -> Result<Response, ApiError> {
+    if !is_action_request(&payload) {
+        return rest::create::<Salary>(&ctx, payload).await;
     }
+
+    let action = match parse_request(payload)? {
+        SalaryRequest::Adjust { emp_no, adjustment } => return Ok(encrypted(&adjust_salary(&ctx.state, &ctx.audit, emp_no, adjustment).await?)?.into_response()),
+        SalaryRequest::AdjustDepartment { dept_no, adjustment, dry_run } => return Ok(encrypted(&adjust_department_salaries(&ctx.state, &ctx.audit, dept_no, adjustment, dry_run).await?)?.into_response()),
+        request => request.into_action()?,
+    };
+
+    execute(&ctx, action).await
 }
 
-async fn create_salary(state: &Arc<AppState>, salary_value: Value) -> Result<Json<String>, StatusCode> {
-    let new_salary: Salary = from_value(salary_value).map_err(|_| StatusCode::BAD_REQUEST)?;
-
-    let query_str = "INSERT INTO salaries (emp_no, salary, from_date, to_date) VALUES (?, ?, ?, ?)";
-
-    let result = query(query_str)
-        .bind(new_salary.emp_no)
-        .bind(new_salary.salary)
-        .bind(&new_salary.from_date)
-        .bind(&new_salary.to_date)
-        .execute(&state.db)
-        .await;
-
-    match result {
-        Ok(_) => {
-            let json_data = to_value(new_salary).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error creating salary: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+// Closes the employee's current salary row at the effective date and opens a new one from it.
+#[instrument(name = "db.adjust_salary", skip_all, fields(emp_no = emp_no))]
+pub async fn adjust_salary(state: &Arc<AppState>, audit: &AuditContext, emp_no: i32, adjustment: SalaryAdjustment) -> Result<SalaryAdjustmentResult, ApiError> {
+    adjustment.validate()?;
+
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!(error = ?e, "Error starting salary adjustment");
+        ApiError::from(e)
+    })?;
+
+    let query_str = "SELECT * FROM salaries WHERE emp_no = ? AND to_date = ? AND deleted_at IS NULL FOR UPDATE";
+    let current = query_as::<_, Salary>(query_str)
+        .bind(emp_no)
+        .bind(open_end_date())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error fetching current salary");
+            ApiError::from(e)
+        })?
+        .ok_or_else(|| ApiError::not_found("Employee has no current salary"))?;
+
+    let after = adjusted_amount(current.salary, &adjustment)?;
+    let result = apply_adjustment(&mut tx, audit, &current, after, adjustment.effective_date).await?;
+
+    tx.commit().await.map_err(|e| {
+        error!(error = ?e, "Error committing salary adjustment");
+        ApiError::from(e)
+    })?;
+
+    Ok(result)
 }
 
-async fn read_all_salaries(state: &Arc<AppState>) -> Result<Json<String>, StatusCode> {
-    let query_str = "SELECT * FROM salaries LIMIT 10";
+// Applies a percentage adjustment to everyone currently in the department.
+// With `dry_run` the figures are computed but nothing is written.
+#[instrument(name = "db.adjust_department_salaries", skip_all, fields(dept_no = %dept_no, dry_run = dry_run))]
+pub async fn adjust_department_salaries(state: &Arc<AppState>, audit: &AuditContext, dept_no: String, adjustment: SalaryAdjustment, dry_run: bool) -> Result<DepartmentAdjustmentResult, ApiError> {
+    adjustment.validate()?;
 
-    let salaries = query_as::<_, Salary>(query_str)
-        .fetch_all(&state.db)
-        .await;
+    // A single absolute figure for a whole department is almost certainly a mistake.
+    if adjustment.amount.is_some() {
+        let mut errors = ValidationErrors::default();
+        errors.add("adjustment.amount", "department adjustments take a percent, not an amount");
+        return Err(errors.into());
+    }
 
-    match salaries {
-        Ok(data) => {
-            let json_data = to_value(data).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
-        }
-        Err(e) => {
-            eprintln!("Error fetching salaries: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    // A dry run only reads, so it takes no row locks.
+    let adjustments = if dry_run {
+        let mut conn = state.db.acquire().await.map_err(ApiError::from)?;
+        let current_salaries = department_salaries(&mut conn, &dept_no, false).await?;
+        current_salaries.iter()
+            .map(|current| preview_adjustment(current, adjusted_amount(current.salary, &adjustment)?, adjustment.effective_date))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        let mut tx = state.db.begin().await.map_err(|e| {
+            error!(error = ?e, "Error starting department salary adjustment");
+            ApiError::from(e)
+        })?;
+
+        let current_salaries = department_salaries(&mut tx, &dept_no, true).await?;
+        let mut adjustments = Vec::with_capacity(current_salaries.len());
+        for current in &current_salaries {
+            let after = adjusted_amount(current.salary, &adjustment)?;
+            adjustments.push(apply_adjustment(&mut tx, audit, current, after, adjustment.effective_date).await?);
         }
+
+        tx.commit().await.map_err(|e| {
+            error!(error = ?e, "Error committing department salary adjustment");
+            ApiError::from(e)
+        })?;
+        adjustments
+    };
+
+    let summary = DepartmentAdjustmentResult {
+        dept_no,
+        effective_date: adjustment.effective_date,
+        dry_run,
+        total_before: adjustments.iter().map(|a| a.before as i64).sum(),
+        total_after: adjustments.iter().map(|a| a.after as i64).sum(),
+        adjustments,
+    };
+
+    Ok(summary)
+}
+
+// The current salaries of everyone currently in the department, locked when `lock` is set.
+// An unknown or deleted department is a 404 rather than an empty adjustment.
+async fn department_salaries(conn: &mut MySqlConnection, dept_no: &str, lock: bool) -> Result<Vec<Salary>, ApiError> {
+    let department = query("SELECT dept_no FROM departments WHERE dept_no = ? AND deleted_at IS NULL")
+        .bind(dept_no)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error fetching department");
+            ApiError::from(e)
+        })?;
+    if department.is_none() {
+        return Err(ApiError::not_found("Department not found"));
     }
+
+    let query_str = format!(
+        "SELECT s.* FROM salaries s \
+        INNER JOIN dept_emp d ON d.emp_no = s.emp_no \
+        WHERE d.dept_no = ? AND d.to_date = ? AND s.to_date = ? \
+        AND d.deleted_at IS NULL AND s.deleted_at IS NULL \
+        ORDER BY s.emp_no{}",
+        if lock { " FOR UPDATE" } else { "" }
+    );
+    query_as::<_, Salary>(&query_str)
+        .bind(dept_no)
+        .bind(open_end_date())
+        .bind(open_end_date())
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error fetching department salaries");
+            ApiError::from(e)
+        })
 }
 
-async fn read_salary(state: &Arc<AppState>, emp_no: i32, from_date: NaiveDate) -> Result<Json<String>, StatusCode> {
-    let query_str = "SELECT * FROM salaries WHERE emp_no = ? AND from_date = ?";
-    let salary = query_as::<_, Salary>(query_str)
-        .bind(emp_no)
-        .bind(from_date)
-        .fetch_one(&state.db)
-        .await;
-
-    match salary {
-        Ok(data) => {
-            let json_data = to_value(data).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
+fn adjusted_amount(before: i32, adjustment: &SalaryAdjustment) -> Result<i32, ApiError> {
+    let after = match (adjustment.amount, adjustment.percent) {
+        (Some(amount), None) => amount,
+        (None, Some(percent)) => {
+            // `as` would saturate at i32::MAX instead of failing.
+            let after = (before as f64 * (1.0 + percent / 100.0)).round();
+            if after > i32::MAX as f64 {
+                return Err(ApiError::bad_request("adjusted salary does not fit a salary column"));
+            }
+            after as i32
         }
-        Err(_) => Err(StatusCode::NOT_FOUND),
+        _ => return Err(ApiError::bad_request("adjustment needs exactly one of amount or percent")),
+    };
+
+    if after <= 0 {
+        return Err(ApiError::bad_request("adjusted salary must be greater than zero"));
     }
+    Ok(after)
 }
 
-async fn update_salary(state: &Arc<AppState>, emp_no: i32, from_date: NaiveDate, salary_value: Value) -> Result<Json<String>, StatusCode> {
-    let mut updated_salary: Salary = from_value(salary_value).map_err(|_| StatusCode::BAD_REQUEST)?;
+fn preview_adjustment(current: &Salary, after: i32, effective_date: NaiveDate) -> Result<SalaryAdjustmentResult, ApiError> {
+    // salaries is keyed on (emp_no, from_date), so the new row must start after the current one
+    if effective_date <= current.from_date {
+        return Err(ApiError::bad_request("effective_date must be after the current salary's from_date"));
+    }
+
+    Ok(SalaryAdjustmentResult {
+        emp_no: current.emp_no,
+        before: current.salary,
+        after,
+        effective_date,
+    })
+}
 
-    //Use emp_no and from_date from path parameter
-    updated_salary.emp_no = emp_no;
-    updated_salary.from_date = from_date;
+async fn apply_adjustment(tx: &mut Transaction<'_, MySql>, audit: &AuditContext, current: &Salary, after: i32, effective_date: NaiveDate) -> Result<SalaryAdjustmentResult, ApiError> {
+    let result = preview_adjustment(current, after, effective_date)?;
 
-    let query_str = "UPDATE salaries SET salary = ?, to_date = ? WHERE emp_no = ? AND from_date = ?";
+    query("UPDATE salaries SET to_date = ?, row_version = row_version + 1 WHERE emp_no = ? AND from_date = ?")
+        .bind(effective_date)
+        .bind(current.emp_no)
+        .bind(current.from_date)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error closing salary");
+            ApiError::from(e)
+        })?;
 
-    let result = query(query_str)
-        .bind(updated_salary.salary)
-        .bind(&updated_salary.to_date)
-        .bind(emp_no)
-        .bind(from_date)
-        .execute(&state.db)
-        .await;
-
-    match result {
-        Ok(_) => {
-            let json_data = to_value(updated_salary).unwrap();
-            let key = get_key();
-            let encrypted_data = encrypt_json(&json_data, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            Ok(Json(encrypted_data))
+    query("INSERT INTO salaries (emp_no, salary, from_date, to_date) VALUES (?, ?, ?, ?)")
+        .bind(current.emp_no)
+        .bind(after)
+        .bind(effective_date)
+        .bind(open_end_date())
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Error inserting adjusted salary");
+            ApiError::from(e)
+        })?;
+
+    let closed = Salary { to_date: effective_date, row_version: current.row_version + 1, ..*current };
+    let opened = Salary { salary: after, from_date: effective_date, to_date: open_end_date(), row_version: initial_row_version(), ..*current };
+    audit.record(tx, "adjust", "salaries", json!({"emp_no": current.emp_no, "from_date": current.from_date}), Some(current), Some(&closed)).await?;
+    audit.record(tx, "adjust", "salaries", json!({"emp_no": current.emp_no, "from_date": effective_date}), None, Some(&opened)).await?;
+
+    Ok(result)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjustment(amount: Option<i32>, percent: Option<f64>) -> SalaryAdjustment {
+        SalaryAdjustment { amount, percent, effective_date: "2002-01-01".parse().unwrap() }
+    }
+
+    fn salary() -> Salary {
+        Salary {
+            emp_no: 10001,
+            salary: 60117,
+            from_date: "2001-06-22".parse().unwrap(),
+            to_date: open_end_date(),
+            row_version: initial_row_version(),
+            deleted_at: None,
         }
-        Err(e) => {
-            eprintln!("Error updating salary: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn bad_request(result: Result<impl std::fmt::Debug, ApiError>) -> String {
+        match result {
+            Err(ApiError::BadRequest(message)) => message,
+            other => panic!("expected a bad request, got {:?}", other),
         }
     }
-}
 
-async fn delete_salary(state: &Arc<AppState>, emp_no: i32, from_date: NaiveDate) -> Result<Json<String>, StatusCode> {
-    let query_str = "DELETE FROM salaries WHERE emp_no = ? AND from_date = ?";
+    #[test]
+    fn an_amount_replaces_the_salary() {
+        assert_eq!(adjusted_amount(60117, &adjustment(Some(70000), None)).unwrap(), 70000);
+    }
 
-    let result = query(query_str)
-        .bind(emp_no)
-        .bind(from_date)
-        .execute(&state.db)
-        .await;
-
-    match result {
-        Ok(_) => Ok(Json(String::from("Salary deleted successfully"))),
-        Err(e) => {
-            eprintln!("Error deleting salary: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    #[test]
+    fn a_percent_scales_the_salary_and_rounds() {
+        assert_eq!(adjusted_amount(60000, &adjustment(None, Some(10.0))).unwrap(), 66000);
+        assert_eq!(adjusted_amount(60000, &adjustment(None, Some(-25.0))).unwrap(), 45000);
+        // 60117 * 1.035 = 62221.095, and 50001 * 1.001 = 50051.001
+        assert_eq!(adjusted_amount(60117, &adjustment(None, Some(3.5))).unwrap(), 62221);
+        assert_eq!(adjusted_amount(50001, &adjustment(None, Some(0.1))).unwrap(), 50051);
+        // 3 * 1.5 = 4.5 rounds half away from zero
+        assert_eq!(adjusted_amount(3, &adjustment(None, Some(50.0))).unwrap(), 5);
+    }
+
+    #[test]
+    fn exactly_one_of_amount_or_percent_is_needed() {
+        assert!(bad_request(adjusted_amount(60117, &adjustment(Some(1), Some(1.0)))).contains("exactly one"));
+        assert!(bad_request(adjusted_amount(60117, &adjustment(None, None))).contains("exactly one"));
+    }
+
+    #[test]
+    fn the_result_must_stay_positive() {
+        assert!(bad_request(adjusted_amount(60117, &adjustment(Some(0), None))).contains("greater than zero"));
+        assert!(bad_request(adjusted_amount(1, &adjustment(None, Some(-60.0)))).contains("greater than zero"));
+        assert!(bad_request(adjusted_amount(60117, &adjustment(None, Some(-100.0)))).contains("greater than zero"));
+    }
+
+    #[test]
+    fn a_result_past_i32_is_rejected_rather_than_clamped() {
+        assert!(bad_request(adjusted_amount(i32::MAX, &adjustment(None, Some(1.0)))).contains("does not fit"));
+        assert!(bad_request(adjusted_amount(60117, &adjustment(None, Some(1e12)))).contains("does not fit"));
+        assert_eq!(adjusted_amount(i32::MAX - 1, &adjustment(None, Some(0.0))).unwrap(), i32::MAX - 1);
+    }
+
+    #[test]
+    fn the_preview_reports_before_and_after() {
+        let preview = preview_adjustment(&salary(), 66000, "2002-01-01".parse().unwrap()).unwrap();
+        assert_eq!((preview.emp_no, preview.before, preview.after), (10001, 60117, 66000));
+        assert_eq!(preview.effective_date, "2002-01-01".parse::<NaiveDate>().unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn the_new_row_must_start_after_the_current_one() {
+        let current = salary();
+        assert!(bad_request(preview_adjustment(&current, 66000, current.from_date)).contains("effective_date"));
+        assert!(bad_request(preview_adjustment(&current, 66000, "2000-01-01".parse().unwrap())).contains("effective_date"));
+    }
+}
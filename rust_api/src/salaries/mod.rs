@@ -1,3 +1,2 @@
 pub mod handlers;
-pub mod models;
-pub mod routes;
\ No newline at end of file
+pub mod routes;
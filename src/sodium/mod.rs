@@ -1 +0,0 @@
-pub mod sodium_crypto;
\ No newline at end of file